@@ -0,0 +1,255 @@
+use crate::prelude::{
+    measured_encode, size_var_int, DraxReadExt, DraxResult, DraxWriteExt, PacketComponent, Size,
+    TransportError,
+};
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::future::Future;
+use std::io::{Read, Write};
+use std::marker::PhantomData;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+use tokio::io::AsyncWriteExt;
+
+/// The largest zlib stream produced by compressing `len` bytes, mirroring zlib's `compressBound`.
+pub(crate) const fn compress_bound(len: usize) -> usize {
+    len.saturating_add(len >> 12)
+        .saturating_add(len >> 14)
+        .saturating_add(len >> 25)
+        .saturating_add(13)
+}
+
+pub(crate) fn deflate(bytes: &[u8]) -> DraxResult<Vec<u8>> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(bytes)?;
+    Ok(encoder.finish()?)
+}
+
+/// Inflates a zlib stream which must hold exactly `len` bytes, never inflating past `len`.
+pub(crate) fn inflate(compressed: &[u8], len: usize) -> DraxResult<Vec<u8>> {
+    let mut body = Vec::with_capacity(len);
+    ZlibDecoder::new(compressed)
+        .take(len as u64 + 1)
+        .read_to_end(&mut body)?;
+    if body.len() > len {
+        return TransportError::limit_exceeded(
+            len as i32,
+            body.len() as i32,
+            "inflating compressed component",
+        );
+    }
+    if body.len() < len {
+        return Err(TransportError::EOF);
+    }
+    Ok(body)
+}
+
+/// Wakes the thread parked in [`encode_blocking`].
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Encodes the component into a buffer from a synchronous context.
+///
+/// Encoding into a `Vec` never waits on I/O, so this normally completes on the first poll. An
+/// encode which does yield parks the thread until it is woken.
+fn encode_blocking<T: PacketComponent<C>, C: Send + Sync>(
+    component: &T::ComponentType,
+    context: &mut C,
+) -> DraxResult<Vec<u8>> {
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(measured_encode::<T, C>(component, context));
+    loop {
+        if let Poll::Ready(result) = future.as_mut().poll(&mut cx) {
+            return Ok(result?.0);
+        }
+        thread::park();
+    }
+}
+
+/// A delegate struct which encodes and decodes a `T` compressed with zlib.
+///
+/// The component is laid out as `[VarInt uncompressed length][VarInt compressed length]` followed
+/// by the deflated body. Uncompressed lengths above `MAX` are rejected with
+/// `TransportError::LimitExceeded` on both sides, a decode checks the declared length before
+/// anything is inflated and never inflates past it, guarding against decompression bombs. The
+/// inner component must consume the entire inflated body.
+///
+/// The compressed length is only known once the body is deflated, so `size` encodes and
+/// deflates the inner component to measure it.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let blob = "a".repeat(1024);
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<Compressed<String, 2048>>(&blob).await?;
+/// assert!(cursor.get_ref().len() < blob.len());
+/// cursor.set_position(0);
+/// let back = cursor.decode_component::<Compressed<String, 2048>>().await?;
+/// assert_eq!(back, blob);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Compressed<T, const MAX: usize>(PhantomData<T>);
+
+impl<C: Send + Sync, T, const MAX: usize> PacketComponent<C> for Compressed<T, MAX>
+where
+    T: PacketComponent<C>,
+{
+    type ComponentType = T::ComponentType;
+
+    decode!(read, context {
+        let uncompressed_len = read.read_var_int().await?;
+        let lim = i32::try_from(MAX).unwrap_or(i32::MAX);
+        if uncompressed_len < 0 || uncompressed_len > lim {
+            return TransportError::limit_exceeded(
+                lim,
+                uncompressed_len,
+                "decoding compressed component",
+            );
+        }
+        let uncompressed_len = uncompressed_len as usize;
+
+        let compressed_len = read.read_var_int().await?;
+        if compressed_len < 0 {
+            return TransportError::limit_exceeded(
+                0,
+                compressed_len,
+                "decoding compressed component",
+            );
+        }
+        let compressed = read
+            .read_exact_limited(compressed_len as usize, compress_bound(uncompressed_len))
+            .await?;
+
+        let body = inflate(&compressed, uncompressed_len)?;
+
+        let mut slice = body.as_slice();
+        let component = T::decode(context, &mut slice).await?;
+        if !slice.is_empty() {
+            return TransportError::trailing_bytes(slice.len() as u64);
+        }
+        Ok(component)
+    });
+
+    encode!(component_ref, write, context {
+        let (body, len) = measured_encode::<T, C>(component_ref, context).await?;
+        if len > MAX {
+            return TransportError::limit_exceeded(
+                i32::try_from(MAX).unwrap_or(i32::MAX),
+                i32::try_from(len).unwrap_or(i32::MAX),
+                "encoding compressed component",
+            );
+        }
+        let compressed = deflate(&body)?;
+        write.write_var_int(len as i32).await?;
+        write.write_var_int(compressed.len() as i32).await?;
+        write.write_all(&compressed).await?;
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        let body = encode_blocking::<T, C>(input, context)?;
+        let compressed = deflate(&body)?.len();
+        Ok(Size::Dynamic(
+            size_var_int(body.len() as i32) + size_var_int(compressed as i32) + compressed,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::delegate::compression::Compressed;
+    use crate::prelude::{
+        DraxReadExt, DraxResult, DraxWriteExt, LengthDelimited, PacketComponent, Size,
+        TransportError, VarInt,
+    };
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use std::assert_matches::assert_matches;
+    use std::io::{Cursor, Write};
+
+    #[tokio::test]
+    pub async fn test_compressed_round_trip() -> DraxResult<()> {
+        let values: Vec<i32> = (0..512).map(|n| n % 4).collect();
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<Compressed<Vec<VarInt>, 1024>>(&values)
+            .await?;
+        assert_eq!(
+            Compressed::<Vec<VarInt>, 1024>::size(&values, &mut ())?,
+            Size::Dynamic(cursor.get_ref().len())
+        );
+        assert!(cursor.get_ref().len() < 514);
+
+        let mut framed = Cursor::new(vec![]);
+        framed
+            .encode_framed::<Compressed<Vec<VarInt>, 1024>>(&values)
+            .await?;
+        framed
+            .encode_framed::<LengthDelimited<Compressed<Vec<VarInt>, 1024>>>(&values)
+            .await?;
+        framed.set_position(0);
+        assert_eq!(
+            framed.read_var_int().await? as usize,
+            cursor.get_ref().len()
+        );
+
+        cursor.set_position(0);
+        let back = cursor
+            .decode_component::<Compressed<Vec<VarInt>, 1024>>()
+            .await?;
+        assert_eq!(back, values);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_compressed_over_max() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<Compressed<String, 2048>>(&"a".repeat(1024))
+            .await?;
+        let error = cursor
+            .encode_component::<Compressed<String, 1024>>(&"a".repeat(1024))
+            .await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(1024, 1026, _)));
+        cursor.set_position(0);
+        let error = cursor.decode_component::<Compressed<String, 512>>().await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(512, 1026, _)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_compressed_bomb_rejected() -> DraxResult<()> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&vec![0; 1 << 20])?;
+        let bomb = encoder.finish()?;
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(4096).await?;
+        cursor.write_var_int(bomb.len() as i32).await?;
+        cursor.get_mut().extend_from_slice(&bomb);
+        cursor.set_position(0);
+
+        let error = cursor.decode_component::<Compressed<Vec<u8>, 4096>>().await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(4096, 4097, _)));
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(16).await?;
+        cursor.write_var_int(bomb.len() as i32).await?;
+        cursor.get_mut().extend_from_slice(&bomb);
+        cursor.set_position(0);
+        let error = cursor.decode_component::<Compressed<Vec<u8>, 4096>>().await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(29, 1039, _)));
+        Ok(())
+    }
+}
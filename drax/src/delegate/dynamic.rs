@@ -0,0 +1,764 @@
+use crate::delegate::primitive::size_var_int;
+use crate::prelude::{
+    DraxReadExt, DraxResult, DraxWriteExt, LengthDelimited, PacketComponent, Size, TransportError,
+};
+use crate::transport::{return_scratch, take_scratch};
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// A boxed future returned by object safe packet components.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// A reader trait object accepted by object safe packet components.
+pub type DynRead<'a> = dyn AsyncRead + Unpin + Send + Sync + 'a;
+
+/// A writer trait object accepted by object safe packet components.
+pub type DynWrite<'a> = dyn AsyncWrite + Unpin + Send + Sync + 'a;
+
+/// A value produced by an object safe packet component.
+pub type DynValue = Box<dyn Any + Send + Sync>;
+
+/// An object safe version of `PacketComponent`, allowing components to be selected at runtime.
+///
+/// Values are passed around as `dyn Any` and are expected to be the `ComponentType` of the
+/// component which produced them.
+pub trait DynPacketComponent<C: Send + Sync>: Send + Sync {
+    fn decode_dyn<'a>(
+        &'a self,
+        context: &'a mut C,
+        read: &'a mut DynRead<'_>,
+    ) -> BoxFuture<'a, DraxResult<DynValue>>;
+
+    fn encode_dyn<'a>(
+        &'a self,
+        value: &'a (dyn Any + Send + Sync),
+        context: &'a mut C,
+        write: &'a mut DynWrite<'_>,
+    ) -> BoxFuture<'a, DraxResult<()>>;
+
+    fn size_dyn(&self, value: &(dyn Any + Send + Sync), context: &mut C) -> DraxResult<Size>;
+}
+
+struct Registered<P> {
+    id: i32,
+    _phantom: PhantomData<fn() -> P>,
+}
+
+impl<C: Send + Sync, P> DynPacketComponent<C> for Registered<P>
+where
+    P: PacketComponent<C> + 'static,
+    P::ComponentType: 'static,
+{
+    fn decode_dyn<'a>(
+        &'a self,
+        context: &'a mut C,
+        read: &'a mut DynRead<'_>,
+    ) -> BoxFuture<'a, DraxResult<DynValue>> {
+        Box::pin(async move {
+            let value = LengthDelimited::<P>::decode(context, read).await?;
+            Ok(Box::new(value) as DynValue)
+        })
+    }
+
+    fn encode_dyn<'a>(
+        &'a self,
+        value: &'a (dyn Any + Send + Sync),
+        context: &'a mut C,
+        write: &'a mut DynWrite<'_>,
+    ) -> BoxFuture<'a, DraxResult<()>> {
+        Box::pin(async move {
+            match value.downcast_ref::<P::ComponentType>() {
+                Some(value) => LengthDelimited::<P>::encode(value, context, write).await,
+                None => Err(TransportError::DynamicTypeMismatch(self.id)),
+            }
+        })
+    }
+
+    fn size_dyn(&self, value: &(dyn Any + Send + Sync), context: &mut C) -> DraxResult<Size> {
+        match value.downcast_ref::<P::ComponentType>() {
+            Some(value) => LengthDelimited::<P>::size(value, context),
+            None => Err(TransportError::DynamicTypeMismatch(self.id)),
+        }
+    }
+}
+
+/// Decodes the body of a [`FnComponent`].
+pub type DecodeFn<C, T> =
+    for<'a> fn(&'a mut C, &'a mut DynRead<'_>) -> BoxFuture<'a, DraxResult<T>>;
+
+/// Encodes the body of a [`FnComponent`].
+pub type EncodeFn<C, T> =
+    for<'a> fn(&'a T, &'a mut C, &'a mut DynWrite<'_>) -> BoxFuture<'a, DraxResult<()>>;
+
+/// Sizes the body of a [`FnComponent`].
+pub type SizeFn<C, T> = fn(&T, &mut C) -> DraxResult<Size>;
+
+/// An object safe packet component built from function pointers rather than a type, for one-off
+/// components registered with [`Registry::register_fn`].
+///
+/// The body is framed by its VarInt length exactly like components registered with
+/// [`Registry::register`], the functions only read and write the body itself. The id is only
+/// used to report a `TransportError::DynamicTypeMismatch`.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # async fn test() -> DraxResult<()> {
+/// let component = FnComponent::<(), i32>::new(
+///     0,
+///     |_, read| Box::pin(async move { read.read_var_int().await }),
+///     |value, _, write| Box::pin(async move { write.write_var_int(*value).await }),
+///     |value, _| Ok(Size::Dynamic(size_var_int(*value))),
+/// );
+///
+/// let mut bytes = vec![];
+/// component.encode_dyn(&300, &mut (), &mut bytes).await?;
+/// assert_eq!(bytes, vec![2, 172, 2]);
+///
+/// let value = component.decode_dyn(&mut (), &mut bytes.as_slice()).await?;
+/// assert_eq!(value.downcast_ref::<i32>(), Some(&300));
+/// # Ok(())
+/// # }
+/// ```
+pub struct FnComponent<C, T> {
+    id: i32,
+    decode: DecodeFn<C, T>,
+    encode: EncodeFn<C, T>,
+    size: SizeFn<C, T>,
+}
+
+impl<C, T> FnComponent<C, T> {
+    pub fn new(
+        id: i32,
+        decode: DecodeFn<C, T>,
+        encode: EncodeFn<C, T>,
+        size: SizeFn<C, T>,
+    ) -> Self {
+        Self {
+            id,
+            decode,
+            encode,
+            size,
+        }
+    }
+}
+
+impl<C: Send + Sync, T> DynPacketComponent<C> for FnComponent<C, T>
+where
+    T: Send + Sync + 'static,
+{
+    fn decode_dyn<'a>(
+        &'a self,
+        context: &'a mut C,
+        read: &'a mut DynRead<'_>,
+    ) -> BoxFuture<'a, DraxResult<DynValue>> {
+        Box::pin(async move {
+            let len = read.read_var_int().await?;
+            if len < 0 {
+                return TransportError::limit_exceeded(0, len, "decoding frame length");
+            }
+
+            let mut limited = (&mut *read).take(len as u64);
+            let value = (self.decode)(context, &mut limited).await?;
+            if limited.limit() != 0 {
+                return TransportError::trailing_bytes(limited.limit());
+            }
+            Ok(Box::new(value) as DynValue)
+        })
+    }
+
+    fn encode_dyn<'a>(
+        &'a self,
+        value: &'a (dyn Any + Send + Sync),
+        context: &'a mut C,
+        write: &'a mut DynWrite<'_>,
+    ) -> BoxFuture<'a, DraxResult<()>> {
+        Box::pin(async move {
+            let Some(value) = value.downcast_ref::<T>() else {
+                return Err(TransportError::DynamicTypeMismatch(self.id));
+            };
+            let mut buffer = Vec::new();
+            (self.encode)(value, context, &mut buffer).await?;
+            write.write_var_int(buffer.len() as i32).await?;
+            write.write_all(&buffer).await?;
+            Ok(())
+        })
+    }
+
+    fn size_dyn(&self, value: &(dyn Any + Send + Sync), context: &mut C) -> DraxResult<Size> {
+        match value.downcast_ref::<T>() {
+            Some(value) => {
+                let size = (self.size)(value, context)?.bytes();
+                Ok(Size::Dynamic(size + size_var_int(size as i32)))
+            }
+            None => Err(TransportError::DynamicTypeMismatch(self.id)),
+        }
+    }
+}
+
+/// A registry of packet components keyed by their VarInt id, used by [`DynamicUnion`].
+pub struct Registry<C: Send + Sync> {
+    components: HashMap<i32, Box<dyn DynPacketComponent<C>>>,
+    capture_unknown: bool,
+}
+
+impl<C: Send + Sync> Registry<C> {
+    pub fn new() -> Self {
+        Self {
+            components: HashMap::new(),
+            capture_unknown: false,
+        }
+    }
+
+    /// Registers the component `P` under the given id, replacing any previous registration.
+    pub fn register<P>(&mut self, id: i32) -> &mut Self
+    where
+        P: PacketComponent<C> + 'static,
+        P::ComponentType: 'static,
+    {
+        self.components.insert(
+            id,
+            Box::new(Registered::<P> {
+                id,
+                _phantom: PhantomData,
+            }),
+        );
+        self
+    }
+
+    /// Registers a [`FnComponent`] decoding and encoding a `T` under the given id, replacing any
+    /// previous registration.
+    pub fn register_fn<T>(
+        &mut self,
+        id: i32,
+        decode: DecodeFn<C, T>,
+        encode: EncodeFn<C, T>,
+        size: SizeFn<C, T>,
+    ) -> &mut Self
+    where
+        C: 'static,
+        T: Send + Sync + 'static,
+    {
+        self.components
+            .insert(id, Box::new(FnComponent::new(id, decode, encode, size)));
+        self
+    }
+
+    /// Sets whether unknown ids are captured as raw bytes rather than rejected.
+    pub fn capture_unknown(&mut self, capture_unknown: bool) -> &mut Self {
+        self.capture_unknown = capture_unknown;
+        self
+    }
+
+    pub fn get(&self, id: i32) -> Option<&dyn DynPacketComponent<C>> {
+        self.components.get(&id).map(|component| component.as_ref())
+    }
+}
+
+impl<C: Send + Sync> Default for Registry<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Defines a context which carries a [`Registry`] for [`DynamicUnion`] to dispatch on.
+pub trait HasRegistry: Send + Sync + Sized {
+    fn registry(&self) -> Arc<Registry<Self>>;
+}
+
+/// The body of a decoded [`DynamicUnion`].
+#[derive(Debug)]
+pub enum DynamicBody {
+    /// The value decoded by the component registered for the id.
+    Known(DynValue),
+    /// The raw bytes of a body whose id is not registered.
+    Unknown(Vec<u8>),
+}
+
+/// A value decoded by [`DynamicUnion`].
+#[derive(Debug)]
+pub struct DynamicPacket {
+    pub id: i32,
+    pub body: DynamicBody,
+}
+
+impl DynamicPacket {
+    /// Returns the known body as `T`, or `None` if the body is unknown or of another type.
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        match &self.body {
+            DynamicBody::Known(value) => value.downcast_ref(),
+            DynamicBody::Unknown(_) => None,
+        }
+    }
+}
+
+/// A delegate struct which encodes and decodes a union whose variant is selected by a VarInt id
+/// looked up in the [`Registry`] of the context.
+///
+/// The union is laid out as `[VarInt id][VarInt length][body]`, the body being framed allows
+/// ids which are not registered to be captured verbatim and forwarded when the registry is set
+/// to capture unknown ids. Otherwise an unknown id fails with `TransportError::InvalidVariant`.
+pub struct DynamicUnion;
+
+impl<C: HasRegistry> PacketComponent<C> for DynamicUnion {
+    type ComponentType = DynamicPacket;
+
+    decode!(read, context {
+        let id = read.read_var_int().await?;
+        let registry = context.registry();
+        let body = match registry.get(id) {
+            Some(component) => {
+                let mut read = &mut *read;
+                DynamicBody::Known(component.decode_dyn(context, &mut read).await?)
+            }
+            None if registry.capture_unknown => {
+                let len = read.read_var_int().await?;
+                if len < 0 {
+                    return TransportError::limit_exceeded(0, len, "decoding frame length");
+                }
+                let mut bytes = Vec::new();
+                (&mut *read).take(len as u64).read_to_end(&mut bytes).await?;
+                if bytes.len() != len as usize {
+                    return Err(TransportError::EOF);
+                }
+                DynamicBody::Unknown(bytes)
+            }
+            None => return TransportError::invalid_variant(id),
+        };
+        Ok(DynamicPacket { id, body })
+    });
+
+    encode!(component_ref, write, context {
+        write.write_var_int(component_ref.id).await?;
+        match &component_ref.body {
+            DynamicBody::Known(value) => {
+                let registry = context.registry();
+                let Some(component) = registry.get(component_ref.id) else {
+                    return TransportError::invalid_variant(component_ref.id);
+                };
+                let mut write = &mut *write;
+                component.encode_dyn(value.as_ref(), context, &mut write).await?;
+            }
+            DynamicBody::Unknown(bytes) => {
+                write.write_var_int(bytes.len() as i32).await?;
+                write.write_all(bytes).await?;
+            }
+        }
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        let body = match &input.body {
+            DynamicBody::Known(value) => {
+                let registry = context.registry();
+                match registry.get(input.id) {
+                    Some(component) => component.size_dyn(value.as_ref(), context)?,
+                    None => return TransportError::invalid_variant(input.id),
+                }
+            }
+            DynamicBody::Unknown(bytes) => {
+                Size::Dynamic(bytes.len() + size_var_int(bytes.len() as i32))
+            }
+        };
+        Ok(body + size_var_int(input.id))
+    }
+}
+
+/// Decodes the body of a [`RuntimeEnum`] variant.
+pub type VariantDecoder<C> =
+    for<'a> fn(&'a mut C, &'a mut DynRead<'_>) -> BoxFuture<'a, DraxResult<DynValue>>;
+
+/// Encodes the body of a [`RuntimeEnum`] variant.
+pub type VariantEncoder<C> = for<'a> fn(
+    &'a (dyn Any + Send + Sync),
+    &'a mut C,
+    &'a mut DynWrite<'_>,
+) -> BoxFuture<'a, DraxResult<()>>;
+
+/// Sizes the body of a [`RuntimeEnum`] variant.
+pub type VariantSizer<C> = fn(&(dyn Any + Send + Sync), &mut C) -> DraxResult<Size>;
+
+/// The functions decoding, encoding and sizing the body of a [`RuntimeEnum`] variant.
+pub struct RuntimeVariant<C> {
+    decode: VariantDecoder<C>,
+    encode: VariantEncoder<C>,
+    size: VariantSizer<C>,
+}
+
+impl<C> Clone for RuntimeVariant<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for RuntimeVariant<C> {}
+
+impl<C: Send + Sync> RuntimeVariant<C> {
+    pub fn new(
+        decode: VariantDecoder<C>,
+        encode: VariantEncoder<C>,
+        size: VariantSizer<C>,
+    ) -> Self {
+        Self {
+            decode,
+            encode,
+            size,
+        }
+    }
+
+    /// Builds the variant from the packet component `P`, values of any other type fail with
+    /// `TransportError::DynamicTypeMismatch`.
+    pub fn of<P>() -> Self
+    where
+        P: PacketComponent<C> + 'static,
+        P::ComponentType: 'static,
+    {
+        fn decode<'a, C: Send + Sync, P: PacketComponent<C> + 'static>(
+            context: &'a mut C,
+            read: &'a mut DynRead<'_>,
+        ) -> BoxFuture<'a, DraxResult<DynValue>>
+        where
+            P::ComponentType: 'static,
+        {
+            Box::pin(async move { Ok(Box::new(P::decode(context, read).await?) as DynValue) })
+        }
+
+        fn encode<'a, C: Send + Sync, P: PacketComponent<C>>(
+            value: &'a (dyn Any + Send + Sync),
+            context: &'a mut C,
+            write: &'a mut DynWrite<'_>,
+        ) -> BoxFuture<'a, DraxResult<()>>
+        where
+            P::ComponentType: 'static,
+        {
+            Box::pin(async move {
+                match value.downcast_ref::<P::ComponentType>() {
+                    Some(value) => P::encode(value, context, write).await,
+                    None => Err(TransportError::DynamicTypeMismatch(-1)),
+                }
+            })
+        }
+
+        fn size<C: Send + Sync, P: PacketComponent<C>>(
+            value: &(dyn Any + Send + Sync),
+            context: &mut C,
+        ) -> DraxResult<Size>
+        where
+            P::ComponentType: 'static,
+        {
+            match value.downcast_ref::<P::ComponentType>() {
+                Some(value) => P::size(value, context),
+                None => Err(TransportError::DynamicTypeMismatch(-1)),
+            }
+        }
+
+        Self::new(decode::<C, P>, encode::<C, P>, size::<C, P>)
+    }
+}
+
+/// Defines a context which maps the discriminants of a [`RuntimeEnum`] to their variants,
+/// allowing the variants to be assembled at runtime such as by plugins.
+pub trait HasRuntimeVariants: Send + Sync + Sized {
+    fn runtime_variant(&self, discriminant: i32) -> Option<RuntimeVariant<Self>>;
+}
+
+/// A delegate struct which encodes and decodes a VarInt discriminant followed by the body of
+/// the variant the context maps it to, decoding to the discriminant and the boxed value.
+///
+/// Unlike [`DynamicUnion`] the body is not framed, a discriminant without a variant fails with
+/// `TransportError::InvalidVariant` since the length of its body is unknown. A value which is
+/// not of the type of its variant fails with `TransportError::DynamicTypeMismatch`.
+pub struct RuntimeEnum;
+
+impl RuntimeEnum {
+    fn variant<C: HasRuntimeVariants>(
+        context: &C,
+        discriminant: i32,
+    ) -> DraxResult<RuntimeVariant<C>> {
+        match context.runtime_variant(discriminant) {
+            Some(variant) => Ok(variant),
+            None => TransportError::invalid_variant(discriminant),
+        }
+    }
+}
+
+fn with_discriminant<T>(result: DraxResult<T>, discriminant: i32) -> DraxResult<T> {
+    match result {
+        Err(TransportError::DynamicTypeMismatch(_)) => {
+            Err(TransportError::DynamicTypeMismatch(discriminant))
+        }
+        result => result,
+    }
+}
+
+impl<C: HasRuntimeVariants> PacketComponent<C> for RuntimeEnum {
+    type ComponentType = (i32, DynValue);
+
+    decode!(read, context {
+        let discriminant = read.read_var_int().await?;
+        let variant = Self::variant(context, discriminant)?;
+        let mut read = &mut *read;
+        Ok((discriminant, (variant.decode)(context, &mut read).await?))
+    });
+
+    encode!(component_ref, write, context {
+        let (discriminant, value) = component_ref;
+        let variant = Self::variant(context, *discriminant)?;
+        // The body is encoded first so a value of the wrong type leaves nothing in the writer.
+        let mut buffer = take_scratch();
+        let result = async {
+            let mut body: &mut DynWrite<'_> = &mut buffer;
+            with_discriminant(
+                (variant.encode)(value.as_ref(), context, &mut body).await,
+                *discriminant,
+            )?;
+            write.write_var_int(*discriminant).await?;
+            write.write_all(&buffer).await?;
+            DraxResult::Ok(())
+        }
+        .await;
+        return_scratch(buffer);
+        result?;
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        let (discriminant, value) = input;
+        let variant = Self::variant(context, *discriminant)?;
+        let body = with_discriminant((variant.size)(value.as_ref(), context), *discriminant)?;
+        Ok(body + size_var_int(*discriminant))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::delegate::dynamic::{
+        DynPacketComponent, DynValue, DynamicBody, DynamicPacket, DynamicUnion, FnComponent,
+        HasRegistry, HasRuntimeVariants, Registry, RuntimeEnum, RuntimeVariant,
+    };
+    use crate::prelude::{
+        size_var_int, DraxReadExt, DraxResult, DraxWriteExt, PacketComponent, Size, TransportError,
+        VarInt,
+    };
+    use std::assert_matches::assert_matches;
+    use std::collections::HashMap;
+    use std::io::Cursor;
+    use std::sync::Arc;
+    use tokio::io::AsyncWriteExt;
+
+    struct Proxy {
+        registry: Arc<Registry<Proxy>>,
+    }
+
+    impl HasRegistry for Proxy {
+        fn registry(&self) -> Arc<Registry<Self>> {
+            self.registry.clone()
+        }
+    }
+
+    fn proxy(capture_unknown: bool) -> Proxy {
+        let mut registry = Registry::new();
+        registry
+            .register::<VarInt>(0)
+            .register::<String>(1)
+            .capture_unknown(capture_unknown);
+        Proxy {
+            registry: Arc::new(registry),
+        }
+    }
+
+    async fn wire() -> DraxResult<Vec<u8>> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(1).await?;
+        cursor.write_var_int(5).await?;
+        cursor
+            .encode_component::<String>(&"test".to_string())
+            .await?;
+        cursor.write_var_int(300).await?;
+        cursor.write_var_int(3).await?;
+        cursor.write_all(&[9, 8, 7]).await?;
+        Ok(cursor.into_inner())
+    }
+
+    #[tokio::test]
+    pub async fn test_dynamic_union_forwards_unknown() -> DraxResult<()> {
+        let mut context = proxy(true);
+        let bytes = wire().await?;
+        let mut read = bytes.as_slice();
+
+        let known = DynamicUnion::decode(&mut context, &mut read).await?;
+        assert_eq!(known.id, 1);
+        assert_eq!(known.downcast_ref::<String>().unwrap(), "test");
+
+        let unknown = DynamicUnion::decode(&mut context, &mut read).await?;
+        assert_eq!(unknown.id, 300);
+        assert_matches!(&unknown.body, DynamicBody::Unknown(body) if body == &[9, 8, 7]);
+        assert!(read.is_empty());
+
+        let mut forwarded = vec![];
+        DynamicUnion::encode(&known, &mut context, &mut forwarded).await?;
+        DynamicUnion::encode(&unknown, &mut context, &mut forwarded).await?;
+        assert_eq!(forwarded, bytes);
+
+        let size = DynamicUnion::size(&known, &mut context)?;
+        assert_eq!(
+            size + DynamicUnion::size(&unknown, &mut context)?,
+            Size::Dynamic(bytes.len())
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_dynamic_union_rejects_unknown() -> DraxResult<()> {
+        let mut context = proxy(false);
+        let bytes = wire().await?;
+        let mut read = bytes.as_slice();
+
+        DynamicUnion::decode(&mut context, &mut read).await?;
+        let error = DynamicUnion::decode(&mut context, &mut read).await;
+        assert_matches!(error, Err(TransportError::InvalidVariant(300)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_dynamic_union_type_mismatch() -> DraxResult<()> {
+        let mut context = proxy(false);
+        let packet = DynamicPacket {
+            id: 0,
+            body: DynamicBody::Known(Box::new("not an int".to_string())),
+        };
+
+        let error = DynamicUnion::encode(&packet, &mut context, &mut vec![]).await;
+        assert_matches!(error, Err(TransportError::DynamicTypeMismatch(0)));
+        Ok(())
+    }
+
+    fn var_int_component() -> FnComponent<Proxy, i32> {
+        FnComponent::new(
+            7,
+            |_, read| Box::pin(async move { read.read_var_int().await }),
+            |value, _, write| Box::pin(async move { write.write_var_int(*value).await }),
+            |value, _| Ok(Size::Dynamic(size_var_int(*value))),
+        )
+    }
+
+    #[tokio::test]
+    pub async fn test_fn_component_round_trip() -> DraxResult<()> {
+        let mut context = proxy(false);
+        let component = var_int_component();
+
+        let mut bytes = vec![];
+        component.encode_dyn(&300, &mut context, &mut bytes).await?;
+        assert_eq!(bytes, vec![2, 172, 2]);
+        assert_eq!(
+            component.size_dyn(&300, &mut context)?,
+            Size::Dynamic(bytes.len())
+        );
+
+        let value = component
+            .decode_dyn(&mut context, &mut bytes.as_slice())
+            .await?;
+        assert_eq!(value.downcast_ref::<i32>(), Some(&300));
+
+        let error = component
+            .encode_dyn(&"not an int", &mut context, &mut vec![])
+            .await;
+        assert_matches!(error, Err(TransportError::DynamicTypeMismatch(7)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_fn_component_registered() -> DraxResult<()> {
+        let mut registry = Registry::new();
+        registry.register_fn::<i32>(
+            7,
+            |_, read| Box::pin(async move { read.read_var_int().await }),
+            |value, _, write| Box::pin(async move { write.write_var_int(*value).await }),
+            |value, _| Ok(Size::Dynamic(size_var_int(*value))),
+        );
+        let mut context = Proxy {
+            registry: Arc::new(registry),
+        };
+
+        let packet = DynamicPacket {
+            id: 7,
+            body: DynamicBody::Known(Box::new(300)),
+        };
+        let mut bytes = vec![];
+        DynamicUnion::encode(&packet, &mut context, &mut bytes).await?;
+        assert_eq!(bytes, vec![7, 2, 172, 2]);
+
+        let back = DynamicUnion::decode(&mut context, &mut bytes.as_slice()).await?;
+        assert_eq!(back.downcast_ref::<i32>(), Some(&300));
+        Ok(())
+    }
+
+    struct Plugins {
+        variants: HashMap<i32, RuntimeVariant<Plugins>>,
+    }
+
+    impl HasRuntimeVariants for Plugins {
+        fn runtime_variant(&self, discriminant: i32) -> Option<RuntimeVariant<Self>> {
+            self.variants.get(&discriminant).copied()
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_runtime_enum_dispatch() -> DraxResult<()> {
+        let mut plugins = Plugins {
+            variants: HashMap::new(),
+        };
+        plugins.variants.insert(0, RuntimeVariant::of::<VarInt>());
+        plugins.variants.insert(1, RuntimeVariant::of::<String>());
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(0).await?;
+        cursor.write_var_int(300).await?;
+        cursor.write_var_int(1).await?;
+        cursor
+            .encode_component::<String>(&"plugin".to_string())
+            .await?;
+        cursor.write_var_int(2).await?;
+        cursor.set_position(0);
+
+        let (discriminant, value) = RuntimeEnum::decode(&mut plugins, &mut cursor).await?;
+        assert_eq!(discriminant, 0);
+        assert_eq!(value.downcast_ref::<i32>(), Some(&300));
+
+        let (discriminant, value) = RuntimeEnum::decode(&mut plugins, &mut cursor).await?;
+        assert_eq!(discriminant, 1);
+        assert_eq!(value.downcast_ref::<String>().unwrap(), "plugin");
+
+        let error = RuntimeEnum::decode(&mut plugins, &mut cursor).await;
+        assert_matches!(error, Err(TransportError::InvalidVariant(2)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_runtime_enum_encode() -> DraxResult<()> {
+        let mut plugins = Plugins {
+            variants: HashMap::from([(0, RuntimeVariant::of::<VarInt>())]),
+        };
+        let value: (i32, DynValue) = (0, Box::new(300i32));
+        let mut cursor = Cursor::new(vec![]);
+        RuntimeEnum::encode(&value, &mut plugins, &mut cursor).await?;
+        assert_eq!(
+            RuntimeEnum::size(&value, &mut plugins)?,
+            Size::Dynamic(cursor.get_ref().len())
+        );
+
+        cursor.set_position(0);
+        let (discriminant, back) = RuntimeEnum::decode(&mut plugins, &mut cursor).await?;
+        assert_eq!(discriminant, 0);
+        assert_eq!(back.downcast_ref::<i32>(), Some(&300));
+
+        let value: (i32, DynValue) = (0, Box::new("300".to_string()));
+        let mut cursor = Cursor::new(vec![]);
+        let error = RuntimeEnum::encode(&value, &mut plugins, &mut cursor).await;
+        assert_matches!(error, Err(TransportError::DynamicTypeMismatch(0)));
+        assert!(cursor.get_ref().is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,736 @@
+use crate::delegate::primitive::size_var_int;
+use crate::prelude::{
+    DraxReadExt, DraxResult, DraxWriteExt, PacketComponent, Size, TransportError,
+};
+use std::marker::PhantomData;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Defines an enum-like component whose variant is selected by a tag that the component does
+/// not read or write itself.
+///
+/// This is used when the variant of a field is chosen by the value of another field decoded
+/// earlier in the packet, for example an action byte read several fields before the payload.
+/// The surrounding component decodes the tag field normally and then hands it to
+/// `decode_variant` to dispatch.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # use tokio::io::{AsyncRead, AsyncWrite};
+/// #[derive(Debug, PartialEq)]
+/// enum Payload {
+///     Move(i32),
+///     Rename(String),
+/// }
+///
+/// impl<C: Send + Sync> TaggedVariant<C> for Payload {
+///     type Tag = u8;
+///     type ComponentType = Self;
+///
+///     fn tag_of(component_ref: &Self) -> u8 {
+///         match component_ref {
+///             Payload::Move(_) => 0,
+///             Payload::Rename(_) => 1,
+///         }
+///     }
+///
+///     async fn decode_variant<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+///         tag: &u8,
+///         context: &mut C,
+///         read: &mut A,
+///     ) -> DraxResult<Self> {
+///         match tag {
+///             0 => Ok(Payload::Move(VarInt::decode(context, read).await?)),
+///             1 => Ok(Payload::Rename(String::decode(context, read).await?)),
+///             tag => TransportError::invalid_variant(*tag as i32),
+///         }
+///     }
+///
+///     async fn encode_variant<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+///         component_ref: &Self,
+///         context: &mut C,
+///         write: &mut A,
+///     ) -> DraxResult<()> {
+///         match component_ref {
+///             Payload::Move(value) => VarInt::encode(value, context, write).await,
+///             Payload::Rename(value) => String::encode(value, context, write).await,
+///         }
+///     }
+///
+///     fn size_variant(input: &Self, context: &mut C) -> DraxResult<Size> {
+///         match input {
+///             Payload::Move(value) => VarInt::size(value, context),
+///             Payload::Rename(value) => String::size(value, context),
+///         }
+///     }
+/// }
+///
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// Payload::encode_variant(&Payload::Move(10), &mut (), &mut cursor).await?;
+/// cursor.set_position(0);
+/// let back = Payload::decode_variant(&0, &mut (), &mut cursor).await?;
+/// assert_eq!(back, Payload::Move(10));
+/// # Ok(())
+/// # }
+/// ```
+pub trait TaggedVariant<C: Send + Sync> {
+    /// The type of the value which selects the variant.
+    type Tag: Send + Sync;
+
+    /// The type which the variant is decoded into.
+    type ComponentType: Sized + Send + Sync;
+
+    /// Returns the tag which selects the variant of the given value.
+    fn tag_of(component_ref: &Self::ComponentType) -> Self::Tag;
+
+    /// Decodes the variant selected by the given tag from the given reader.
+    async fn decode_variant<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+        tag: &Self::Tag,
+        context: &mut C,
+        read: &mut A,
+    ) -> DraxResult<Self::ComponentType>;
+
+    /// Encodes the variant body to the given writer, the tag is not written.
+    async fn encode_variant<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+        component_ref: &Self::ComponentType,
+        context: &mut C,
+        write: &mut A,
+    ) -> DraxResult<()>;
+
+    /// Calculates the size of the variant body, excluding the tag.
+    fn size_variant(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size>;
+}
+
+/// A delegate struct which encodes and decodes a [`TaggedVariant`] whose discriminant is packed
+/// into the high `BITS` bits of a byte, the low bits carrying a small payload.
+///
+/// The variant is selected by a tag of `(discriminant, payload)`, the payload is handed to the
+/// variant decoder as the initial value of the variant. `BITS` must be between 1 and 7.
+/// Encoding a tag which does not fit in its bits fails with `TransportError::LimitExceeded`.
+pub struct PackedDiscriminant<const BITS: u32, T>(PhantomData<T>);
+
+impl<const BITS: u32, T> PackedDiscriminant<BITS, T> {
+    const PAYLOAD_BITS: u32 = {
+        assert!(
+            BITS > 0 && BITS < 8,
+            "packed discriminant must use between 1 and 7 bits"
+        );
+        8 - BITS
+    };
+    const PAYLOAD_MASK: u8 = (1 << Self::PAYLOAD_BITS) - 1;
+}
+
+impl<C: Send + Sync, const BITS: u32, T> PacketComponent<C> for PackedDiscriminant<BITS, T>
+where
+    T: TaggedVariant<C, Tag = (u8, u8)>,
+{
+    type ComponentType = T::ComponentType;
+
+    decode!(read, context {
+        let byte = read.read_u8().await?;
+        let tag = (byte >> Self::PAYLOAD_BITS, byte & Self::PAYLOAD_MASK);
+        T::decode_variant(&tag, context, read).await
+    });
+
+    encode!(component_ref, write, context {
+        let (discriminant, payload) = T::tag_of(component_ref);
+        if discriminant >> BITS != 0 {
+            return TransportError::limit_exceeded(
+                (1 << BITS) - 1,
+                discriminant as i32,
+                "encoding packed discriminant",
+            );
+        }
+        if payload & !Self::PAYLOAD_MASK != 0 {
+            return TransportError::limit_exceeded(
+                Self::PAYLOAD_MASK as i32,
+                payload as i32,
+                "encoding packed payload",
+            );
+        }
+
+        write.write_u8((discriminant << Self::PAYLOAD_BITS) | payload).await?;
+        T::encode_variant(component_ref, context, write).await?;
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        Ok(T::size_variant(input, context)? + Size::Constant(1))
+    }
+}
+
+/// A delegate struct which encodes and decodes an enum as the VarInt of its `i32` discriminant.
+///
+/// The enum is converted with its `TryFrom<i32>` and `Into<i32>` implementations, such as the
+/// ones generated by `num_enum`. Decoding a discriminant which the enum rejects fails with
+/// `TransportError::InvalidVariant`.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// #[repr(i32)]
+/// enum Hand {
+///     Main = 0,
+///     Off = 1,
+/// }
+///
+/// impl From<Hand> for i32 {
+///     fn from(hand: Hand) -> i32 {
+///         hand as i32
+///     }
+/// }
+///
+/// impl TryFrom<i32> for Hand {
+///     type Error = ();
+///
+///     fn try_from(value: i32) -> Result<Self, ()> {
+///         match value {
+///             0 => Ok(Hand::Main),
+///             1 => Ok(Hand::Off),
+///             _ => Err(()),
+///         }
+///     }
+/// }
+///
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<EnumVarInt<Hand>>(&Hand::Off).await?;
+/// assert_eq!(cursor.get_ref(), &vec![1]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct EnumVarInt<E>(PhantomData<E>);
+
+impl<C: Send + Sync, E> PacketComponent<C> for EnumVarInt<E>
+where
+    E: TryFrom<i32> + Into<i32> + Copy + Send + Sync,
+{
+    type ComponentType = E;
+
+    decode!(read {
+        let discriminant = read.read_var_int().await?;
+        E::try_from(discriminant).or_else(|_| TransportError::invalid_variant(discriminant))
+    });
+
+    encode!(component_ref, write {
+        write.write_var_int((*component_ref).into()).await?;
+    });
+
+    fn size(input: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+        Ok(Size::Dynamic(size_var_int((*input).into())))
+    }
+}
+
+/// A delegate struct which encodes and decodes an optional enum as a single VarInt
+/// discriminant, where zero is `None` and `n` selects the variant tagged `n - 1`.
+///
+/// The variant body follows the discriminant and is handled by the [`TaggedVariant`] impl of
+/// `T`, whose tags are the unshifted `i32` discriminants. Decoding a negative discriminant fails
+/// with `TransportError::InvalidVariant`, as does encoding a tag which cannot be shifted.
+pub struct OptionalEnum<T>(PhantomData<T>);
+
+impl<T> OptionalEnum<T> {
+    fn shifted<C: Send + Sync>(component_ref: &T::ComponentType) -> DraxResult<i32>
+    where
+        T: TaggedVariant<C, Tag = i32>,
+    {
+        let tag = T::tag_of(component_ref);
+        match tag.checked_add(1) {
+            Some(discriminant) if tag >= 0 => Ok(discriminant),
+            _ => TransportError::invalid_variant(tag),
+        }
+    }
+}
+
+impl<C: Send + Sync, T> PacketComponent<C> for OptionalEnum<T>
+where
+    T: TaggedVariant<C, Tag = i32>,
+{
+    type ComponentType = Option<T::ComponentType>;
+
+    decode!(read, context {
+        match read.read_var_int().await? {
+            0 => Ok(None),
+            discriminant if discriminant < 0 => TransportError::invalid_variant(discriminant),
+            discriminant => Ok(Some(T::decode_variant(&(discriminant - 1), context, read).await?)),
+        }
+    });
+
+    encode!(component_ref, write, context {
+        match component_ref {
+            None => write.write_var_int(0).await?,
+            Some(value) => {
+                write.write_var_int(Self::shifted(value)?).await?;
+                T::encode_variant(value, context, write).await?;
+            }
+        }
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        match input {
+            None => Ok(Size::Dynamic(1)),
+            Some(value) => Size::Dynamic(size_var_int(Self::shifted(value)?))
+                .checked_add(T::size_variant(value, context)?),
+        }
+    }
+}
+
+/// A delegate struct which encodes and decodes a choice between two components, prefixed by a
+/// `bool` selecting the branch.
+///
+/// `true` is followed by a `T` and decodes to `Ok`, `false` is followed by an `F` and decodes to
+/// `Err`.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<BoolEnum<VarInt, String>>(&Ok(300)).await?;
+/// assert_eq!(cursor.get_ref(), &vec![1, 172, 2]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct BoolEnum<T, F>(PhantomData<(T, F)>);
+
+impl<C: Send + Sync, T, F> PacketComponent<C> for BoolEnum<T, F>
+where
+    T: PacketComponent<C>,
+    F: PacketComponent<C>,
+{
+    type ComponentType = Result<T::ComponentType, F::ComponentType>;
+
+    decode!(read, context {
+        if bool::decode(context, read).await? {
+            Ok(Ok(T::decode(context, read).await?))
+        } else {
+            Ok(Err(F::decode(context, read).await?))
+        }
+    });
+
+    encode!(component_ref, write, context {
+        bool::encode(&component_ref.is_ok(), context, write).await?;
+        match component_ref {
+            Ok(value) => T::encode(value, context, write).await?,
+            Err(value) => F::encode(value, context, write).await?,
+        }
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        match input {
+            Ok(value) => T::size(value, context)?,
+            Err(value) => F::size(value, context)?,
+        }
+        .checked_add(Size::Constant(1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::delegate::enums::{OptionalEnum, PackedDiscriminant};
+    use crate::prelude::{
+        BoolEnum, DraxReadExt, DraxResult, DraxWriteExt, EnumVarInt, PacketComponent, Size,
+        TaggedVariant, TransportError, VarInt,
+    };
+    use std::assert_matches::assert_matches;
+    use std::io::Cursor;
+    use tokio::io::{AsyncRead, AsyncWrite};
+
+    #[derive(Debug, PartialEq)]
+    enum Payload {
+        Move(i32),
+        Rename(String),
+    }
+
+    impl<C: Send + Sync> TaggedVariant<C> for Payload {
+        type Tag = u8;
+        type ComponentType = Self;
+
+        fn tag_of(component_ref: &Self) -> u8 {
+            match component_ref {
+                Payload::Move(_) => 0,
+                Payload::Rename(_) => 1,
+            }
+        }
+
+        async fn decode_variant<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+            tag: &u8,
+            context: &mut C,
+            read: &mut A,
+        ) -> DraxResult<Self> {
+            match tag {
+                0 => Ok(Payload::Move(VarInt::decode(context, read).await?)),
+                1 => Ok(Payload::Rename(String::decode(context, read).await?)),
+                tag => TransportError::invalid_variant(*tag as i32),
+            }
+        }
+
+        async fn encode_variant<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+            component_ref: &Self,
+            context: &mut C,
+            write: &mut A,
+        ) -> DraxResult<()> {
+            match component_ref {
+                Payload::Move(value) => VarInt::encode(value, context, write).await,
+                Payload::Rename(value) => String::encode(value, context, write).await,
+            }
+        }
+
+        fn size_variant(input: &Self, context: &mut C) -> DraxResult<Size> {
+            match input {
+                Payload::Move(value) => VarInt::size(value, context),
+                Payload::Rename(value) => String::size(value, context),
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Action {
+        mode: u8,
+        entity: i32,
+        payload: Payload,
+    }
+
+    impl<C: Send + Sync> PacketComponent<C> for Action {
+        type ComponentType = Self;
+
+        async fn decode<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+            context: &mut C,
+            read: &mut A,
+        ) -> DraxResult<Self> {
+            let mode = u8::decode(context, read).await?;
+            let entity = VarInt::decode(context, read).await?;
+            let payload = Payload::decode_variant(&mode, context, read).await?;
+            Ok(Action {
+                mode,
+                entity,
+                payload,
+            })
+        }
+
+        async fn encode<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+            component_ref: &Self,
+            context: &mut C,
+            write: &mut A,
+        ) -> DraxResult<()> {
+            u8::encode(&component_ref.mode, context, write).await?;
+            VarInt::encode(&component_ref.entity, context, write).await?;
+            Payload::encode_variant(&component_ref.payload, context, write).await
+        }
+
+        fn size(input: &Self, context: &mut C) -> DraxResult<Size> {
+            Ok(u8::size(&input.mode, context)?
+                + VarInt::size(&input.entity, context)?
+                + Payload::size_variant(&input.payload, context)?)
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_tag_from_prior_field() -> DraxResult<()> {
+        for payload in [Payload::Move(25), Payload::Rename("example".to_string())] {
+            let action = Action {
+                mode: <Payload as TaggedVariant<()>>::tag_of(&payload),
+                entity: 55324,
+                payload,
+            };
+
+            let mut cursor = Cursor::new(vec![]);
+            cursor.encode_own_component(&action).await?;
+            cursor.set_position(0);
+
+            assert_eq!(cursor.decode_own_component::<Action>().await?, action);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_tag_from_unknown_variant() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![2]);
+        cursor.set_position(1);
+        cursor.write_var_int(10).await?;
+        cursor.set_position(0);
+
+        let error = cursor.decode_own_component::<Action>().await;
+
+        assert_matches!(error, Err(TransportError::InvalidVariant(2)));
+        Ok(())
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Block {
+        Air,
+        Stone(u8),
+        Custom(u8, i32),
+    }
+
+    impl<C: Send + Sync> TaggedVariant<C> for Block {
+        type Tag = (u8, u8);
+        type ComponentType = Self;
+
+        fn tag_of(component_ref: &Self) -> (u8, u8) {
+            match component_ref {
+                Block::Air => (0, 0),
+                Block::Stone(variant) => (1, *variant),
+                Block::Custom(variant, _) => (2, *variant),
+            }
+        }
+
+        async fn decode_variant<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+            tag: &(u8, u8),
+            context: &mut C,
+            read: &mut A,
+        ) -> DraxResult<Self> {
+            match tag {
+                (0, _) => Ok(Block::Air),
+                (1, variant) => Ok(Block::Stone(*variant)),
+                (2, variant) => Ok(Block::Custom(
+                    *variant,
+                    VarInt::decode(context, read).await?,
+                )),
+                (discriminant, _) => TransportError::invalid_variant(*discriminant as i32),
+            }
+        }
+
+        async fn encode_variant<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+            component_ref: &Self,
+            context: &mut C,
+            write: &mut A,
+        ) -> DraxResult<()> {
+            match component_ref {
+                Block::Custom(_, extra) => VarInt::encode(extra, context, write).await,
+                _ => Ok(()),
+            }
+        }
+
+        fn size_variant(input: &Self, context: &mut C) -> DraxResult<Size> {
+            match input {
+                Block::Custom(_, extra) => VarInt::size(extra, context),
+                _ => Ok(Size::Constant(0)),
+            }
+        }
+    }
+
+    type PackedBlock = PackedDiscriminant<2, Block>;
+
+    #[tokio::test]
+    pub async fn test_packed_discriminant() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<PackedBlock>(&Block::Stone(45))
+            .await?;
+        assert_eq!(cursor.get_ref(), &vec![0b01_101101]);
+
+        cursor.set_position(0);
+        let back = cursor.decode_component::<PackedBlock>().await?;
+        assert_eq!(back, Block::Stone(45));
+
+        let block = Block::Custom(63, 300);
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<PackedBlock>(&block).await?;
+        assert_eq!(cursor.get_ref(), &vec![0b10_111111, 172, 2]);
+        assert_eq!(PackedBlock::size(&block, &mut ())?, Size::Dynamic(3));
+
+        cursor.set_position(0);
+        assert_eq!(cursor.decode_component::<PackedBlock>().await?, block);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_packed_payload_overflow() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        let error = cursor
+            .encode_component::<PackedBlock>(&Block::Stone(64))
+            .await;
+
+        assert_matches!(
+            error,
+            Err(TransportError::LimitExceeded(
+                63,
+                64,
+                "encoding packed payload"
+            ))
+        );
+        assert!(cursor.get_ref().is_empty());
+        Ok(())
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[repr(i32)]
+    enum GameMode {
+        Survival = 0,
+        Creative = 1,
+        Spectator = 300,
+    }
+
+    impl From<GameMode> for i32 {
+        fn from(mode: GameMode) -> i32 {
+            mode as i32
+        }
+    }
+
+    impl TryFrom<i32> for GameMode {
+        type Error = i32;
+
+        fn try_from(value: i32) -> Result<Self, i32> {
+            match value {
+                0 => Ok(GameMode::Survival),
+                1 => Ok(GameMode::Creative),
+                300 => Ok(GameMode::Spectator),
+                value => Err(value),
+            }
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_enum_var_int() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<EnumVarInt<GameMode>>(&GameMode::Spectator)
+            .await?;
+        assert_eq!(cursor.get_ref(), &vec![172, 2]);
+        assert_eq!(
+            EnumVarInt::<GameMode>::size(&GameMode::Creative, &mut ())?,
+            Size::Dynamic(1)
+        );
+
+        cursor.set_position(0);
+        assert_eq!(
+            cursor.decode_component::<EnumVarInt<GameMode>>().await?,
+            GameMode::Spectator
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_enum_var_int_invalid_discriminant() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![2]);
+        let error = cursor.decode_component::<EnumVarInt<GameMode>>().await;
+
+        assert_matches!(error, Err(TransportError::InvalidVariant(2)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_bool_enum_true_branch() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<BoolEnum<VarInt, String>>(&Ok(300))
+            .await?;
+        assert_eq!(cursor.get_ref(), &vec![1, 172, 2]);
+        assert_eq!(
+            BoolEnum::<VarInt, String>::size(&Ok(300), &mut ())?,
+            Size::Dynamic(3)
+        );
+
+        cursor.set_position(0);
+        assert_eq!(
+            cursor
+                .decode_component::<BoolEnum<VarInt, String>>()
+                .await?,
+            Ok(300)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_bool_enum_false_branch() -> DraxResult<()> {
+        let value = Err("reason".to_string());
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<BoolEnum<VarInt, String>>(&value)
+            .await?;
+        assert_eq!(cursor.get_ref()[0], 0);
+        assert_eq!(
+            BoolEnum::<VarInt, String>::size(&value, &mut ())?,
+            Size::Dynamic(8)
+        );
+
+        cursor.set_position(0);
+        assert_eq!(
+            cursor
+                .decode_component::<BoolEnum<VarInt, String>>()
+                .await?,
+            value
+        );
+        Ok(())
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Effect {
+        Glow,
+        Speed(u8),
+    }
+
+    impl<C: Send + Sync> TaggedVariant<C> for Effect {
+        type Tag = i32;
+        type ComponentType = Self;
+
+        fn tag_of(component_ref: &Self) -> i32 {
+            match component_ref {
+                Effect::Glow => 0,
+                Effect::Speed(_) => 1,
+            }
+        }
+
+        async fn decode_variant<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+            tag: &i32,
+            context: &mut C,
+            read: &mut A,
+        ) -> DraxResult<Self> {
+            match tag {
+                0 => Ok(Effect::Glow),
+                1 => Ok(Effect::Speed(u8::decode(context, read).await?)),
+                tag => TransportError::invalid_variant(*tag),
+            }
+        }
+
+        async fn encode_variant<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+            component_ref: &Self,
+            context: &mut C,
+            write: &mut A,
+        ) -> DraxResult<()> {
+            match component_ref {
+                Effect::Glow => Ok(()),
+                Effect::Speed(value) => u8::encode(value, context, write).await,
+            }
+        }
+
+        fn size_variant(input: &Self, _: &mut C) -> DraxResult<Size> {
+            match input {
+                Effect::Glow => Ok(Size::Constant(0)),
+                Effect::Speed(_) => Ok(Size::Constant(1)),
+            }
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_optional_enum() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<OptionalEnum<Effect>>(&None)
+            .await?;
+        cursor
+            .encode_component::<OptionalEnum<Effect>>(&Some(Effect::Glow))
+            .await?;
+        cursor
+            .encode_component::<OptionalEnum<Effect>>(&Some(Effect::Speed(3)))
+            .await?;
+        assert_eq!(cursor.get_ref(), &vec![0, 1, 2, 3]);
+        assert_eq!(
+            OptionalEnum::<Effect>::size(&Some(Effect::Speed(3)), &mut ())?,
+            Size::Dynamic(2)
+        );
+
+        cursor.set_position(0);
+        for expected in [None, Some(Effect::Glow), Some(Effect::Speed(3))] {
+            let back = cursor.decode_component::<OptionalEnum<Effect>>().await?;
+            assert_eq!(back, expected);
+        }
+
+        let mut bytes: &[u8] = &[3];
+        let error = bytes.decode_component::<OptionalEnum<Effect>>().await;
+        assert_matches!(error, Err(TransportError::InvalidVariant(2)));
+        Ok(())
+    }
+}
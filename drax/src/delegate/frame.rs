@@ -0,0 +1,735 @@
+use crate::delegate::primitive::size_var_int;
+use crate::io::FrameRemaining;
+use crate::prelude::{
+    measured_encode, DraxReadExt, DraxResult, DraxWriteExt, PacketComponent, Size, TransportError,
+};
+use std::marker::PhantomData;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// A delegate struct which encodes and decodes a `T` prefixed by its length in bytes as a
+/// VarInt.
+///
+/// The inner component is decoded from a reader bounded to the declared length, it cannot
+/// read past the end of the frame. If the component does not consume the entire frame a
+/// `TransportError::TrailingBytes` error is returned. The prefix is measured from the encoded
+/// body rather than taken from `T::size`, so it always agrees with the body.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<LengthDelimited<VarInt>>(&300).await?;
+/// assert_eq!(cursor.clone().into_inner(), vec![2, 172, 2]);
+/// cursor.set_position(0);
+/// let back = cursor.decode_component::<LengthDelimited<VarInt>>().await?;
+/// assert_eq!(back, 300);
+/// # Ok(())
+/// # }
+/// ```
+pub struct LengthDelimited<T>(PhantomData<T>);
+
+impl<C: Send + Sync, T> PacketComponent<C> for LengthDelimited<T>
+where
+    T: PacketComponent<C>,
+{
+    type ComponentType = T::ComponentType;
+
+    decode!(read, context {
+        let len = read.read_var_int().await?;
+        if len < 0 {
+            return TransportError::limit_exceeded(0, len, "decoding frame length");
+        }
+
+        let mut limited = (&mut *read).take(len as u64);
+        let component = T::decode(context, &mut limited).await?;
+        if limited.limit() != 0 {
+            return TransportError::trailing_bytes(limited.limit());
+        }
+        Ok(component)
+    });
+
+    encode!(component_ref, write, context {
+        let (buffer, len) = measured_encode::<T, C>(component_ref, context).await?;
+        write.write_var_int(len as i32).await?;
+        write.write_all(&buffer).await?;
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        let size = T::size(input, context)?.as_usize();
+        Ok(Size::Dynamic(size + size_var_int(size as i32)))
+    }
+}
+
+/// A delegate struct which encodes and decodes a `T` prefixed by its length in bytes as a
+/// big-endian `u32`.
+///
+/// This is the fixed-width analog of [`LengthDelimited`], the inner component is decoded from a
+/// reader bounded to the declared length and must consume all of it. Lengths above `MAX` are
+/// rejected with `TransportError::LimitExceeded` before anything is read from the frame.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<U32Prefixed<VarInt>>(&300).await?;
+/// assert_eq!(cursor.get_ref(), &vec![0, 0, 0, 2, 172, 2]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct U32Prefixed<T, const MAX: u32 = { u32::MAX }>(PhantomData<T>);
+
+impl<C: Send + Sync, T, const MAX: u32> PacketComponent<C> for U32Prefixed<T, MAX>
+where
+    T: PacketComponent<C>,
+{
+    type ComponentType = T::ComponentType;
+
+    decode!(read, context {
+        let len = read.read_u32().await?;
+        if len > MAX {
+            return TransportError::limit_exceeded(
+                i32::try_from(MAX).unwrap_or(i32::MAX),
+                i32::try_from(len).unwrap_or(i32::MAX),
+                "decoding u32 frame length",
+            );
+        }
+
+        let mut limited = (&mut *read).take(len as u64);
+        let component = T::decode(context, &mut limited).await?;
+        if limited.limit() != 0 {
+            return TransportError::trailing_bytes(limited.limit());
+        }
+        Ok(component)
+    });
+
+    encode!(component_ref, write, context {
+        let mut buffer = Vec::new();
+        T::encode(component_ref, context, &mut buffer).await?;
+        let len = match u32::try_from(buffer.len()) {
+            Ok(len) if len <= MAX => len,
+            _ => {
+                return TransportError::limit_exceeded(
+                    i32::try_from(MAX).unwrap_or(i32::MAX),
+                    i32::try_from(buffer.len()).unwrap_or(i32::MAX),
+                    "encoding u32 frame length",
+                );
+            }
+        };
+        write.write_u32(len).await?;
+        write.write_all(&buffer).await?;
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        Size::Dynamic(4).checked_add(T::size(input, context)?)
+    }
+}
+
+/// Provides the [`FrameRemaining`] of the frame currently being decoded.
+///
+/// The frame is populated by the codec, either by [`TrackedFrame`] or by a custom codec which
+/// knows the frame length up front.
+pub trait HasRemaining {
+    fn frame_remaining(&mut self) -> &mut FrameRemaining;
+}
+
+impl HasRemaining for FrameRemaining {
+    fn frame_remaining(&mut self) -> &mut FrameRemaining {
+        self
+    }
+}
+
+/// A delegate struct which encodes and decodes a `T` prefixed by its length in bytes as a
+/// VarInt, tracking the bytes remaining in the frame in the context.
+///
+/// This is laid out the same as [`LengthDelimited`], but while `T` is decoded the context holds
+/// the remaining length of this frame, which is what [`RestOfFrame`] fields rely on. The frame
+/// of the surrounding component is restored once `T` is decoded.
+pub struct TrackedFrame<T>(PhantomData<T>);
+
+impl<C: HasRemaining + Send + Sync, T> PacketComponent<C> for TrackedFrame<T>
+where
+    T: PacketComponent<C>,
+{
+    type ComponentType = T::ComponentType;
+
+    decode!(read, context {
+        let len = read.read_var_int().await?;
+        if len < 0 {
+            return TransportError::limit_exceeded(0, len, "decoding frame length");
+        }
+
+        let frame = FrameRemaining::new(len as u64);
+        let mut tracked = frame.track((&mut *read).take(len as u64));
+        let outer = std::mem::replace(context.frame_remaining(), frame);
+        let component = T::decode(context, &mut tracked).await;
+        *context.frame_remaining() = outer;
+
+        let component = component?;
+        if tracked.get_ref().limit() != 0 {
+            return TransportError::trailing_bytes(tracked.get_ref().limit());
+        }
+        Ok(component)
+    });
+
+    encode!(component_ref, write, context {
+        LengthDelimited::<T>::encode(component_ref, context, write).await?;
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        LengthDelimited::<T>::size(input, context)
+    }
+}
+
+/// A delegate struct which decodes `T` from the rest of the current frame.
+///
+/// The reader handed to `T` is bounded to the bytes remaining in the frame according to the
+/// context, so a trailing field which reads until the end of its reader, such as [`ByteDrain`],
+/// stops at the frame boundary even if the underlying reader holds the next packet. Encoding
+/// writes `T` as is.
+///
+/// [`ByteDrain`]: crate::delegate::vec::ByteDrain
+pub struct RestOfFrame<T>(PhantomData<T>);
+
+impl<C: HasRemaining + Send + Sync, T> PacketComponent<C> for RestOfFrame<T>
+where
+    T: PacketComponent<C>,
+{
+    type ComponentType = T::ComponentType;
+
+    decode!(read, context {
+        let remaining = context.frame_remaining().remaining();
+        T::decode(context, &mut (&mut *read).take(remaining)).await
+    });
+
+    encode!(component_ref, write, context {
+        T::encode(component_ref, context, write).await?;
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        T::size(input, context)
+    }
+}
+
+/// A delegate struct which encodes and decodes a `Vec<T>` laid out the same as `Vec<T>`, checking
+/// its elements against the current frame.
+///
+/// An element count which is plausible on its own may still claim more elements than fit in
+/// the frame. Rather than surfacing the EOF raised by the element which runs into the frame
+/// boundary, decoding returns `TransportError::FrameUnderflow`, telling a corrupt count apart
+/// from a stream which ended. An EOF raised while the frame still has bytes remaining is
+/// returned as is. The frame is read from the context as with [`RestOfFrame`].
+pub struct FramedVec<T>(PhantomData<T>);
+
+impl<C: HasRemaining + Send + Sync, T> PacketComponent<C> for FramedVec<T>
+where
+    T: PacketComponent<C>,
+{
+    type ComponentType = Vec<T::ComponentType>;
+
+    decode!(read, context {
+        let len = read.read_var_int().await?;
+        if len < 0 {
+            return TransportError::limit_exceeded(0, len, "decoding framed vec length");
+        }
+        let remaining = context.frame_remaining().remaining();
+        let mut vec = Vec::with_capacity((len as u64).min(remaining) as usize);
+        for _ in 0..len {
+            let before = context.frame_remaining().remaining();
+            match T::decode(context, read).await {
+                Ok(element) => vec.push(element),
+                Err(error) if error.is_eof() && context.frame_remaining().remaining() == 0 => {
+                    return TransportError::frame_underflow(before > 0);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(vec)
+    });
+
+    encode!(component_ref, write, context {
+        Vec::<T>::encode(component_ref, context, write).await?;
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        Vec::<T>::size(input, context)
+    }
+}
+
+/// A delegate struct which decodes `T`, or its default if the current frame has already been
+/// consumed entirely.
+///
+/// This allows a packet to gain trailing fields in a later revision of the protocol while still
+/// decoding frames sent by older peers, which end before the new fields. The frame is read from
+/// the context as with [`RestOfFrame`]. Encoding always writes `T`.
+pub struct TrailingDefault<T>(PhantomData<T>);
+
+impl<C: HasRemaining + Send + Sync, T> PacketComponent<C> for TrailingDefault<T>
+where
+    T: PacketComponent<C>,
+    T::ComponentType: Default,
+{
+    type ComponentType = T::ComponentType;
+
+    decode!(read, context {
+        if context.frame_remaining().remaining() == 0 {
+            return Ok(T::ComponentType::default());
+        }
+        T::decode(context, read).await
+    });
+
+    encode!(component_ref, write, context {
+        T::encode(component_ref, context, write).await?;
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        T::size(input, context)
+    }
+}
+
+/// A packet whose body is kept as raw bytes, allowing it to be forwarded without being
+/// understood.
+///
+/// The packet is decoded as a VarInt id followed by every remaining byte of the reader, it is
+/// meant to be decoded from a bounded frame such as [`LengthDelimited`]. Encoding writes the id
+/// and body back verbatim.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![4, 42, 1, 2, 3]);
+/// let packet = cursor.decode_component::<LengthDelimited<RawPacket>>().await?;
+/// assert_eq!(packet.id, 42);
+/// assert_eq!(packet.body, vec![1, 2, 3]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RawPacket {
+    pub id: i32,
+    pub body: Vec<u8>,
+}
+
+impl<C: Send + Sync> PacketComponent<C> for RawPacket {
+    type ComponentType = Self;
+
+    decode!(read {
+        let id = read.read_var_int().await?;
+        let mut body = Vec::new();
+        read.read_to_end(&mut body).await?;
+        Ok(RawPacket { id, body })
+    });
+
+    encode!(component_ref, write {
+        write.write_var_int(component_ref.id).await?;
+        write.write_all(&component_ref.body).await?;
+    });
+
+    fn size(input: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+        Ok(Size::Dynamic(size_var_int(input.id) + input.body.len()))
+    }
+}
+
+/// A delegate struct which encodes and decodes a `T` as a self-validating frame.
+///
+/// The frame is laid out as `[VarInt length][body][u32 CRC32]` where the checksum is computed
+/// over the body. When decoding the checksum is validated before the body is decoded, and the
+/// body must be consumed exactly by `T`.
+#[cfg(feature = "checksum")]
+pub struct Validated<T>(PhantomData<T>);
+
+#[cfg(feature = "checksum")]
+impl<C: Send + Sync, T> PacketComponent<C> for Validated<T>
+where
+    T: PacketComponent<C>,
+{
+    type ComponentType = T::ComponentType;
+
+    decode!(read, context {
+        let len = read.read_var_int().await?;
+        if len < 0 {
+            return TransportError::limit_exceeded(0, len, "decoding frame length");
+        }
+
+        let mut body = Vec::new();
+        (&mut *read).take(len as u64).read_to_end(&mut body).await?;
+        if body.len() != len as usize {
+            return Err(TransportError::EOF);
+        }
+
+        let expected = read.read_u32().await?;
+        let computed = crc32fast::hash(&body);
+        if expected != computed {
+            return Err(TransportError::ChecksumMismatch(expected, computed));
+        }
+
+        let mut slice = body.as_slice();
+        let component = T::decode(context, &mut slice).await?;
+        if !slice.is_empty() {
+            return TransportError::trailing_bytes(slice.len() as u64);
+        }
+        Ok(component)
+    });
+
+    encode!(component_ref, write, context {
+        let mut buffer = Vec::new();
+        T::encode(component_ref, context, &mut buffer).await?;
+        write.write_var_int(buffer.len() as i32).await?;
+        write.write_all(&buffer).await?;
+        write.write_u32(crc32fast::hash(&buffer)).await?;
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        Ok(LengthDelimited::<T>::size(input, context)? + 4)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::delegate::frame::{
+        FramedVec, HasRemaining, RestOfFrame, TrackedFrame, TrailingDefault,
+    };
+    use crate::io::FrameRemaining;
+    use crate::prelude::{
+        ByteDrain, DraxReadExt, DraxResult, DraxWriteExt, LengthDelimited, PacketComponent,
+        RawPacket, Size, TransportError, U32Prefixed, Validated, VarInt, VecU8,
+    };
+    use std::assert_matches::assert_matches;
+    use std::io::Cursor;
+    use tokio::io::{AsyncRead, AsyncWrite};
+
+    #[tokio::test]
+    pub async fn test_length_delimited_trailing_bytes() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![3, 10, 20, 30]);
+
+        let error = cursor.decode_component::<LengthDelimited<VarInt>>().await;
+
+        assert_matches!(error, Err(TransportError::TrailingBytes(2)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_u32_prefixed() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<U32Prefixed<String>>(&"body".to_string())
+            .await?;
+        cursor.write_var_int(7).await?;
+        assert_eq!(&cursor.get_ref()[..4], &[0, 0, 0, 5]);
+        assert_eq!(
+            U32Prefixed::<String>::size(&"body".to_string(), &mut ())?,
+            Size::Dynamic(9)
+        );
+        cursor.set_position(0);
+
+        assert_eq!(
+            cursor.decode_component::<U32Prefixed<String>>().await?,
+            "body"
+        );
+        assert_eq!(cursor.position(), 9);
+        assert_eq!(cursor.read_var_int().await?, 7);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_u32_prefixed_over_max() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![0xFF, 0xFF, 0xFF, 0xF0, 1, 2, 3]);
+        let error = cursor
+            .decode_component::<U32Prefixed<ByteDrain, 1024>>()
+            .await;
+
+        assert_matches!(
+            error,
+            Err(TransportError::LimitExceeded(
+                1024,
+                i32::MAX,
+                "decoding u32 frame length"
+            ))
+        );
+        assert_eq!(cursor.position(), 4);
+
+        let mut cursor = Cursor::new(vec![]);
+        let error = cursor
+            .encode_component::<U32Prefixed<ByteDrain, 2>>(&vec![1, 2, 3])
+            .await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(2, 3, _)));
+        assert!(cursor.get_ref().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_raw_packet_passthrough() -> DraxResult<()> {
+        let mut body = Cursor::new(vec![]);
+        body.write_var_int(812).await?;
+        body.encode_component::<String>(&"body".to_string()).await?;
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<VecU8>(body.get_ref()).await?;
+        let original = cursor.get_ref().clone();
+        cursor.set_position(0);
+
+        let packet = cursor
+            .decode_component::<LengthDelimited<RawPacket>>()
+            .await?;
+        assert_eq!(packet.id, 812);
+
+        let mut forwarded = Cursor::new(vec![]);
+        forwarded
+            .encode_component::<LengthDelimited<RawPacket>>(&packet)
+            .await?;
+        assert_eq!(forwarded.into_inner(), original);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_validated_frame() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<Validated<String>>(&"test string".to_string())
+            .await?;
+        assert_eq!(cursor.get_ref().len(), 1 + 12 + 4);
+        cursor.set_position(0);
+
+        let back = cursor.decode_component::<Validated<String>>().await?;
+        assert_eq!(back, "test string");
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_validated_truncated_length() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(10).await?;
+        cursor.encode_component::<VarInt>(&10).await?;
+        cursor.set_position(0);
+
+        let error = cursor.decode_component::<Validated<VarInt>>().await;
+
+        assert_matches!(error, Err(TransportError::EOF));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_validated_corrupted_body() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<Validated<String>>(&"test string".to_string())
+            .await?;
+        cursor.get_mut()[3] ^= 0xFF;
+        cursor.set_position(0);
+
+        let error = cursor.decode_component::<Validated<String>>().await;
+
+        assert_matches!(error, Err(TransportError::ChecksumMismatch(_, _)));
+        Ok(())
+    }
+
+    struct Chat;
+
+    impl<C: HasRemaining + Send + Sync> PacketComponent<C> for Chat {
+        type ComponentType = (i32, Vec<u8>);
+
+        async fn decode<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+            context: &mut C,
+            read: &mut A,
+        ) -> DraxResult<Self::ComponentType> {
+            let channel = VarInt::decode(context, read).await?;
+            let message = RestOfFrame::<ByteDrain>::decode(context, read).await?;
+            Ok((channel, message))
+        }
+
+        async fn encode<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+            component_ref: &Self::ComponentType,
+            context: &mut C,
+            write: &mut A,
+        ) -> DraxResult<()> {
+            VarInt::encode(&component_ref.0, context, write).await?;
+            RestOfFrame::<ByteDrain>::encode(&component_ref.1, context, write).await
+        }
+
+        fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+            Ok(VarInt::size(&input.0, context)? + ByteDrain::size(&input.1, context)?)
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_rest_of_frame() -> DraxResult<()> {
+        // A codec which knows the frame is 4 bytes long, followed by the next packet.
+        let mut socket = Cursor::new(vec![172, 2, 1, 2, 99, 99]);
+        let mut frame = FrameRemaining::new(4);
+        let mut tracked = frame.track(&mut socket);
+
+        let chat = Chat::decode(&mut frame, &mut tracked).await?;
+        assert_eq!(chat, (300, vec![1, 2]));
+        assert_eq!(frame.remaining(), 0);
+        assert_eq!(socket.position(), 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_tracked_frame() -> DraxResult<()> {
+        let mut frame = FrameRemaining::default();
+        let mut cursor = Cursor::new(vec![]);
+        let chat = (7, vec![10, 20, 30]);
+        TrackedFrame::<Chat>::encode(&chat, &mut frame, &mut cursor).await?;
+        TrackedFrame::<Chat>::encode(&(8, vec![]), &mut frame, &mut cursor).await?;
+        assert_eq!(cursor.get_ref(), &vec![4, 7, 10, 20, 30, 1, 8]);
+        cursor.set_position(0);
+
+        assert_eq!(
+            TrackedFrame::<Chat>::decode(&mut frame, &mut cursor).await?,
+            chat
+        );
+        assert_eq!(
+            TrackedFrame::<Chat>::decode(&mut frame, &mut cursor).await?,
+            (8, vec![])
+        );
+        assert_eq!(frame.remaining(), 0);
+        Ok(())
+    }
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Settings {
+        view_distance: i32,
+        chat_colors: bool,
+    }
+
+    impl<C: Send + Sync> PacketComponent<C> for Settings {
+        type ComponentType = Self;
+
+        async fn decode<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+            context: &mut C,
+            read: &mut A,
+        ) -> DraxResult<Self> {
+            Ok(Settings {
+                view_distance: VarInt::decode(context, read).await?,
+                chat_colors: bool::decode(context, read).await?,
+            })
+        }
+
+        async fn encode<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+            component_ref: &Self,
+            context: &mut C,
+            write: &mut A,
+        ) -> DraxResult<()> {
+            VarInt::encode(&component_ref.view_distance, context, write).await?;
+            bool::encode(&component_ref.chat_colors, context, write).await
+        }
+
+        fn size(input: &Self, context: &mut C) -> DraxResult<Size> {
+            Ok(VarInt::size(&input.view_distance, context)? + Size::Constant(1))
+        }
+    }
+
+    struct JoinBody;
+
+    impl<C: HasRemaining + Send + Sync> PacketComponent<C> for JoinBody {
+        type ComponentType = (String, Settings);
+
+        async fn decode<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+            context: &mut C,
+            read: &mut A,
+        ) -> DraxResult<Self::ComponentType> {
+            let name = String::decode(context, read).await?;
+            let settings = TrailingDefault::<Settings>::decode(context, read).await?;
+            Ok((name, settings))
+        }
+
+        async fn encode<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+            component_ref: &Self::ComponentType,
+            context: &mut C,
+            write: &mut A,
+        ) -> DraxResult<()> {
+            String::encode(&component_ref.0, context, write).await?;
+            TrailingDefault::<Settings>::encode(&component_ref.1, context, write).await
+        }
+
+        fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+            Ok(String::size(&input.0, context)? + Settings::size(&input.1, context)?)
+        }
+    }
+
+    type Join = TrackedFrame<JoinBody>;
+
+    #[tokio::test]
+    pub async fn test_trailing_default_omitted() -> DraxResult<()> {
+        let mut body = Cursor::new(vec![]);
+        body.encode_component::<String>(&"steve".to_string())
+            .await?;
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<VecU8>(body.get_ref()).await?;
+        cursor.set_position(0);
+
+        let mut frame = FrameRemaining::default();
+        let (name, settings) = Join::decode(&mut frame, &mut cursor).await?;
+        assert_eq!(name, "steve");
+        assert_eq!(settings, Settings::default());
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_trailing_default_present() -> DraxResult<()> {
+        let join = (
+            "alex".to_string(),
+            Settings {
+                view_distance: 12,
+                chat_colors: true,
+            },
+        );
+        let mut frame = FrameRemaining::default();
+        let mut cursor = Cursor::new(vec![]);
+        Join::encode(&join, &mut frame, &mut cursor).await?;
+        cursor.set_position(0);
+
+        assert_eq!(Join::decode(&mut frame, &mut cursor).await?, join);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_framed_vec_underflow() -> DraxResult<()> {
+        // A frame sized for 3 u32 elements whose count claims 10.
+        let mut body = vec![10];
+        body.extend_from_slice(&[0; 12]);
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<VecU8>(&body).await?;
+        cursor.set_position(0);
+
+        let mut frame = FrameRemaining::default();
+        let error = TrackedFrame::<FramedVec<u32>>::decode(&mut frame, &mut cursor).await;
+        assert_matches!(
+            error,
+            Err(TransportError::FrameUnderflow {
+                expected_more: false
+            })
+        );
+
+        // The frame ends two bytes into the fourth element.
+        body.extend_from_slice(&[0; 2]);
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<VecU8>(&body).await?;
+        cursor.set_position(0);
+        let error = TrackedFrame::<FramedVec<u32>>::decode(&mut frame, &mut cursor).await;
+        assert_matches!(
+            error,
+            Err(TransportError::FrameUnderflow {
+                expected_more: true
+            })
+        );
+
+        // The stream ends before the frame does.
+        let mut cursor = Cursor::new(vec![20, 10, 0, 0, 0, 0]);
+        let error = TrackedFrame::<FramedVec<u32>>::decode(&mut frame, &mut cursor).await;
+        assert!(error.is_err_and(|e| e.is_eof()));
+
+        let values = vec![1, 2, 3];
+        let mut cursor = Cursor::new(vec![]);
+        TrackedFrame::<FramedVec<u32>>::encode(&values, &mut frame, &mut cursor).await?;
+        cursor.set_position(0);
+        assert_eq!(
+            TrackedFrame::<FramedVec<u32>>::decode(&mut frame, &mut cursor).await?,
+            values
+        );
+        Ok(())
+    }
+}
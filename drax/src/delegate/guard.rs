@@ -0,0 +1,646 @@
+use crate::prelude::{DraxReadExt, DraxResult, PacketComponent, Size, TransportError};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+/// Tracks the total number of collection elements decoded across a packet.
+///
+/// Each counted collection accounts for the number of elements it declares before allocating,
+/// bounding the total work of nested collections whose individual lengths are modest but whose
+/// product is not.
+#[derive(Debug, Clone)]
+pub struct ElementCounter {
+    limit: usize,
+    count: usize,
+}
+
+impl ElementCounter {
+    pub fn new(limit: usize) -> Self {
+        Self { limit, count: 0 }
+    }
+
+    /// Returns the number of elements accounted for so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Resets the count, allowing the counter to be reused for the next packet.
+    pub fn reset(&mut self) {
+        self.count = 0;
+    }
+
+    /// Accounts for the given number of elements, failing if the total exceeds the limit.
+    pub fn account(&mut self, elements: usize) -> DraxResult<()> {
+        let count = self.count.saturating_add(elements);
+        if count > self.limit {
+            return TransportError::limit_exceeded(
+                self.limit.min(i32::MAX as usize) as i32,
+                count.min(i32::MAX as usize) as i32,
+                "decoding counted elements",
+            );
+        }
+        self.count = count;
+        Ok(())
+    }
+}
+
+/// Defines a context which carries an [`ElementCounter`].
+pub trait HasElementCounter {
+    fn element_counter(&mut self) -> &mut ElementCounter;
+}
+
+impl HasElementCounter for ElementCounter {
+    fn element_counter(&mut self) -> &mut ElementCounter {
+        self
+    }
+}
+
+async fn read_counted_len<
+    C: HasElementCounter,
+    A: tokio::io::AsyncRead + Unpin + Send + Sync + ?Sized,
+>(
+    context: &mut C,
+    read: &mut A,
+) -> DraxResult<usize> {
+    let len = read.read_var_int().await?;
+    if len < 0 {
+        return TransportError::limit_exceeded(0, len, "decoding counted length");
+    }
+    context.element_counter().account(len as usize)?;
+    Ok(len as usize)
+}
+
+/// A delegate struct which encodes and decodes a `Vec<T>`, accounting every decoded element
+/// against the [`ElementCounter`] of the context.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use drax::delegate::guard::{CountedVec, ElementCounter};
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<Vec<Vec<u8>>>(&vec![vec![1, 2], vec![3]]).await?;
+/// cursor.set_position(0);
+///
+/// let mut counter = ElementCounter::new(5);
+/// let back = CountedVec::<CountedVec<u8>>::decode(&mut counter, &mut cursor).await?;
+/// assert_eq!(back, vec![vec![1, 2], vec![3]]);
+/// assert_eq!(counter.count(), 5);
+/// # Ok(())
+/// # }
+/// ```
+pub struct CountedVec<T>(PhantomData<T>);
+
+impl<C: HasElementCounter + Send + Sync, T> PacketComponent<C> for CountedVec<T>
+where
+    T: PacketComponent<C>,
+{
+    type ComponentType = Vec<T::ComponentType>;
+
+    decode!(read, context {
+        let len = read_counted_len(context, read).await?;
+        let mut vec = Vec::with_capacity(len);
+        for _ in 0..len {
+            vec.push(T::decode(context, read).await?);
+        }
+        Ok(vec)
+    });
+
+    encode!(component_ref, write, context {
+        Vec::<T>::encode(component_ref, context, write).await?
+    });
+
+    fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        Vec::<T>::size(component_ref, context)
+    }
+}
+
+/// A delegate struct which encodes and decodes a `HashMap<K, V>`, accounting every decoded
+/// entry against the [`ElementCounter`] of the context.
+pub struct CountedMap<K, V>(PhantomData<(K, V)>);
+
+impl<C: HasElementCounter + Send + Sync, K, V> PacketComponent<C> for CountedMap<K, V>
+where
+    K: PacketComponent<C>,
+    V: PacketComponent<C>,
+    K::ComponentType: Eq + Hash,
+{
+    type ComponentType = HashMap<K::ComponentType, V::ComponentType>;
+
+    decode!(read, context {
+        let len = read_counted_len(context, read).await?;
+        let mut map = HashMap::with_capacity(len);
+        for _ in 0..len {
+            map.insert(
+                K::decode(context, read).await?,
+                V::decode(context, read).await?,
+            );
+        }
+        Ok(map)
+    });
+
+    encode!(component_ref, write, context {
+        HashMap::<K, V>::encode(component_ref, context, write).await?
+    });
+
+    fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        HashMap::<K, V>::size(component_ref, context)
+    }
+}
+
+/// The largest number of elements reserved up front by [`HintedVec`] when the context supplies
+/// no hint and the length is taken from the wire.
+pub const UNTRUSTED_CAPACITY: usize = 1024;
+
+/// Defines a context which may supply a trusted capacity for the next [`HintedVec`] decoded.
+///
+/// The hint is meant for counts the server controls, such as a length decoded and validated by
+/// an earlier field. It is taken when used so it never applies to a nested collection.
+pub trait HasSizeHint {
+    fn take_size_hint(&mut self) -> Option<usize>;
+}
+
+impl HasSizeHint for Option<usize> {
+    fn take_size_hint(&mut self) -> Option<usize> {
+        self.take()
+    }
+}
+
+/// A delegate struct which encodes and decodes a `Vec<T>`, reserving the capacity hinted by the
+/// context before decoding.
+///
+/// Without a hint the declared length is untrusted and at most [`UNTRUSTED_CAPACITY`] elements
+/// are reserved up front, the vector grows as elements are actually decoded.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use drax::delegate::guard::HintedVec;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<Vec<u8>>(&vec![1, 2, 3]).await?;
+/// cursor.set_position(0);
+///
+/// let back = HintedVec::<u8>::decode(&mut Some(64), &mut cursor).await?;
+/// assert_eq!(back, vec![1, 2, 3]);
+/// assert!(back.capacity() >= 64);
+/// # Ok(())
+/// # }
+/// ```
+pub struct HintedVec<T>(PhantomData<T>);
+
+impl<C: HasSizeHint + Send + Sync, T> PacketComponent<C> for HintedVec<T>
+where
+    T: PacketComponent<C>,
+{
+    type ComponentType = Vec<T::ComponentType>;
+
+    decode!(read, context {
+        let len = read.read_var_int().await?;
+        if len < 0 {
+            return TransportError::limit_exceeded(0, len, "decoding hinted length");
+        }
+        let capacity = context
+            .take_size_hint()
+            .unwrap_or((len as usize).min(UNTRUSTED_CAPACITY));
+        let mut vec = Vec::with_capacity(capacity);
+        for _ in 0..len {
+            vec.push(T::decode(context, read).await?);
+        }
+        Ok(vec)
+    });
+
+    encode!(component_ref, write, context {
+        Vec::<T>::encode(component_ref, context, write).await?
+    });
+
+    fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        Vec::<T>::size(component_ref, context)
+    }
+}
+
+/// Tracks the nesting depth of the [`DepthLimited`] components being decoded.
+///
+/// This is the counterpart of the NBT depth limit for user types, bounding recursive types so a
+/// crafted payload cannot nest deep enough to exhaust the stack. Clones share the same depth.
+#[derive(Debug, Clone)]
+pub struct DepthGuard {
+    limit: u32,
+    depth: Arc<AtomicU32>,
+}
+
+impl DepthGuard {
+    pub fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            depth: Arc::new(AtomicU32::new(0)),
+        }
+    }
+
+    /// Returns the current nesting depth.
+    pub fn depth(&self) -> u32 {
+        self.depth.load(Ordering::Relaxed)
+    }
+
+    /// Enters a nested component, failing if the depth would exceed the limit.
+    ///
+    /// The component is left when the returned scope is dropped, including when the decode
+    /// holding it is cancelled part way.
+    pub fn enter(&self) -> DraxResult<DepthScope> {
+        let limit = self.limit;
+        match self
+            .depth
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |depth| {
+                (depth < limit).then_some(depth + 1)
+            }) {
+            Ok(_) => Ok(DepthScope {
+                depth: self.depth.clone(),
+            }),
+            Err(_) => TransportError::depth_exceeded(limit),
+        }
+    }
+}
+
+/// A nesting level entered through [`DepthGuard::enter`], left again when dropped.
+#[derive(Debug)]
+pub struct DepthScope {
+    depth: Arc<AtomicU32>,
+}
+
+impl Drop for DepthScope {
+    fn drop(&mut self) {
+        self.depth.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Defines a context which carries a [`DepthGuard`].
+pub trait HasDepth {
+    fn depth_guard(&mut self) -> &mut DepthGuard;
+}
+
+impl HasDepth for DepthGuard {
+    fn depth_guard(&mut self) -> &mut DepthGuard {
+        self
+    }
+}
+
+/// A delegate struct which encodes and decodes a `T` one level deeper in the [`DepthGuard`] of
+/// the context.
+///
+/// Wrapping the recursive field of a type in `DepthLimited` bounds how deep the type can nest, a
+/// decode past the limit fails with `TransportError::DepthExceeded`. The inner encode and decode
+/// are boxed, allowing a type to refer to itself through this delegate.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use drax::delegate::guard::{DepthGuard, DepthLimited};
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<Vec<Vec<u8>>>(&vec![vec![1, 2]]).await?;
+/// cursor.set_position(0);
+///
+/// let mut guard = DepthGuard::new(1);
+/// let error = Vec::<DepthLimited<Vec<DepthLimited<u8>>>>::decode(&mut guard, &mut cursor).await;
+/// assert!(matches!(error, Err(TransportError::DepthExceeded(1))));
+/// # Ok(())
+/// # }
+/// ```
+pub struct DepthLimited<T>(PhantomData<T>);
+
+impl<C: HasDepth + Send + Sync, T> PacketComponent<C> for DepthLimited<T>
+where
+    T: PacketComponent<C>,
+{
+    type ComponentType = T::ComponentType;
+
+    decode!(read, context {
+        let _scope = context.depth_guard().enter()?;
+        Box::pin(T::decode(context, read)).await
+    });
+
+    encode!(component_ref, write, context {
+        Box::pin(T::encode(component_ref, context, write)).await?
+    });
+
+    fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        T::size(component_ref, context)
+    }
+}
+
+/// Tracks the number of decode operations performed for a packet against a work budget.
+///
+/// Memory and element limits leave out work which allocates little, such as deeply nested or
+/// repeated small components. Every [`Budgeted`] component debits one operation, so a packet
+/// which is small on the wire but expensive to decode runs out of budget.
+#[derive(Debug, Clone)]
+pub struct OpBudget {
+    limit: u64,
+    spent: u64,
+}
+
+impl OpBudget {
+    pub fn new(limit: u64) -> Self {
+        Self { limit, spent: 0 }
+    }
+
+    /// Returns the number of operations debited so far.
+    pub fn spent(&self) -> u64 {
+        self.spent
+    }
+
+    /// Resets the budget, allowing it to be reused for the next packet.
+    pub fn reset(&mut self) {
+        self.spent = 0;
+    }
+
+    /// Debits the given number of operations, failing if the total exceeds the limit.
+    pub fn debit(&mut self, ops: u64) -> DraxResult<()> {
+        let spent = self.spent.saturating_add(ops);
+        if spent > self.limit {
+            return TransportError::limit_exceeded(
+                self.limit.min(i32::MAX as u64) as i32,
+                spent.min(i32::MAX as u64) as i32,
+                "decoding budgeted component",
+            );
+        }
+        self.spent = spent;
+        Ok(())
+    }
+}
+
+/// Defines a context which carries an [`OpBudget`].
+pub trait HasOpBudget {
+    fn op_budget(&mut self) -> &mut OpBudget;
+}
+
+impl HasOpBudget for OpBudget {
+    fn op_budget(&mut self) -> &mut OpBudget {
+        self
+    }
+}
+
+/// A delegate struct which encodes and decodes a `T`, debiting one operation from the
+/// [`OpBudget`] of the context before each decode.
+///
+/// Wrapping the elements and recursive fields of a packet in `Budgeted` makes the debited total
+/// follow the decode work of the packet, a decode past the budget fails with
+/// `TransportError::LimitExceeded` before `T` is read.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use drax::delegate::guard::{Budgeted, OpBudget};
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<Vec<u8>>(&vec![1, 2, 3]).await?;
+/// cursor.set_position(0);
+///
+/// let mut budget = OpBudget::new(2);
+/// let error = Vec::<Budgeted<u8>>::decode(&mut budget, &mut cursor).await;
+/// assert!(matches!(error, Err(TransportError::LimitExceeded(2, 3, _))));
+/// # Ok(())
+/// # }
+/// ```
+pub struct Budgeted<T>(PhantomData<T>);
+
+impl<C: HasOpBudget + Send + Sync, T> PacketComponent<C> for Budgeted<T>
+where
+    T: PacketComponent<C>,
+{
+    type ComponentType = T::ComponentType;
+
+    const FIXED_SIZE: Option<usize> = T::FIXED_SIZE;
+
+    decode!(read, context {
+        context.op_budget().debit(1)?;
+        T::decode(context, read).await
+    });
+
+    encode!(component_ref, write, context {
+        T::encode(component_ref, context, write).await?
+    });
+
+    fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        T::size(component_ref, context)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::delegate::guard::{
+        Budgeted, CountedMap, CountedVec, DepthGuard, DepthLimited, ElementCounter, HasDepth,
+        HintedVec, OpBudget, UNTRUSTED_CAPACITY,
+    };
+    use crate::prelude::{
+        DraxResult, DraxWriteExt, Maybe, PacketComponent, Size, TransportError, VarInt,
+    };
+    use std::assert_matches::assert_matches;
+    use std::collections::HashMap;
+    use std::future::Future;
+    use std::io::Cursor;
+    use std::pin::pin;
+    use std::task::{Context, Waker};
+
+    type Nested = CountedVec<CountedVec<CountedVec<VarInt>>>;
+
+    fn nested(width: usize) -> Vec<Vec<Vec<i32>>> {
+        vec![vec![vec![1; width]; width]; width]
+    }
+
+    #[tokio::test]
+    pub async fn test_nested_within_cap() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<Vec<Vec<Vec<VarInt>>>>(&nested(4))
+            .await?;
+        cursor.set_position(0);
+
+        let mut counter = ElementCounter::new(4 + 16 + 64);
+        let back = Nested::decode(&mut counter, &mut cursor).await?;
+        assert_eq!(back, nested(4));
+        assert_eq!(counter.count(), 84);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_nested_product_exceeds_cap() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<Vec<Vec<Vec<VarInt>>>>(&nested(8))
+            .await?;
+        cursor.set_position(0);
+
+        let mut counter = ElementCounter::new(256);
+        let error = Nested::decode(&mut counter, &mut cursor).await;
+        assert_matches!(
+            error,
+            Err(TransportError::LimitExceeded(
+                256,
+                _,
+                "decoding counted elements"
+            ))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_counted_map_shares_counter() -> DraxResult<()> {
+        let map = HashMap::from([
+            ("first".to_string(), vec![1, 2, 3]),
+            ("second".to_string(), vec![4, 5, 6]),
+        ]);
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<HashMap<String, Vec<VarInt>>>(&map)
+            .await?;
+
+        cursor.set_position(0);
+        let mut counter = ElementCounter::new(8);
+        let back =
+            CountedMap::<String, CountedVec<VarInt>>::decode(&mut counter, &mut cursor).await?;
+        assert_eq!(back, map);
+
+        cursor.set_position(0);
+        let mut counter = ElementCounter::new(7);
+        let error =
+            CountedMap::<String, CountedVec<VarInt>>::decode(&mut counter, &mut cursor).await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(7, 8, _)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_hinted_vec_uses_hint() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<Vec<u8>>(&vec![1, 2]).await?;
+        cursor.set_position(0);
+
+        let mut hint = Some(UNTRUSTED_CAPACITY * 4);
+        let back = HintedVec::<u8>::decode(&mut hint, &mut cursor).await?;
+        assert_eq!(back, vec![1, 2]);
+        assert!(back.capacity() >= UNTRUSTED_CAPACITY * 4);
+        assert_eq!(hint, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_hinted_vec_caps_untrusted_length() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<Vec<u8>>(&vec![1, 2]).await?;
+        cursor.set_position(0);
+
+        let back = HintedVec::<u8>::decode(&mut None, &mut cursor).await?;
+        assert_eq!(back, vec![1, 2]);
+        assert!(back.capacity() < UNTRUSTED_CAPACITY);
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(i32::MAX).await?;
+        cursor.set_position(0);
+        let error = HintedVec::<u8>::decode(&mut None, &mut cursor).await;
+        assert!(error.is_err_and(|error| error.is_eof()));
+        Ok(())
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Chain {
+        next: Option<Box<Chain>>,
+    }
+
+    impl Chain {
+        fn of_length(length: usize) -> Chain {
+            (1..length).fold(Chain { next: None }, |chain, _| Chain {
+                next: Some(Box::new(chain)),
+            })
+        }
+    }
+
+    impl<C: HasDepth + Send + Sync> PacketComponent<C> for Chain {
+        type ComponentType = Self;
+
+        async fn decode<A: tokio::io::AsyncRead + Unpin + Send + Sync + ?Sized>(
+            context: &mut C,
+            read: &mut A,
+        ) -> DraxResult<Self::ComponentType> {
+            let next = Maybe::<DepthLimited<Box<Chain>>>::decode(context, read).await?;
+            Ok(Chain { next })
+        }
+
+        async fn encode<A: tokio::io::AsyncWrite + Unpin + Send + Sync + ?Sized>(
+            component_ref: &Self::ComponentType,
+            context: &mut C,
+            write: &mut A,
+        ) -> DraxResult<()> {
+            Maybe::<DepthLimited<Box<Chain>>>::encode(&component_ref.next, context, write).await
+        }
+
+        fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+            Maybe::<DepthLimited<Box<Chain>>>::size(&input.next, context)
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_nested_depth_limit() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        Chain::encode(&Chain::of_length(5), &mut DepthGuard::new(0), &mut cursor).await?;
+
+        cursor.set_position(0);
+        let mut guard = DepthGuard::new(4);
+        let back = Chain::decode(&mut guard, &mut cursor).await?;
+        assert_eq!(back, Chain::of_length(5));
+        assert_eq!(guard.depth(), 0);
+
+        cursor.set_position(0);
+        let mut guard = DepthGuard::new(3);
+        let error = Chain::decode(&mut guard, &mut cursor).await;
+        assert_matches!(error, Err(TransportError::DepthExceeded(3)));
+        assert_eq!(guard.depth(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_depth_limited_cancelled() -> DraxResult<()> {
+        let (_peer, mut read) = tokio::io::duplex(8);
+        let mut guard = DepthGuard::new(1);
+        {
+            let decode = pin!(DepthLimited::<u8>::decode(&mut guard, &mut read));
+            let poll = decode.poll(&mut Context::from_waker(Waker::noop()));
+            assert!(poll.is_pending());
+        }
+        assert_eq!(guard.depth(), 0);
+        assert!(guard.enter().is_ok());
+        Ok(())
+    }
+
+    type Grid = Vec<Budgeted<Vec<Budgeted<VarInt>>>>;
+
+    #[tokio::test]
+    pub async fn test_op_budget() -> DraxResult<()> {
+        let grid = vec![vec![1; 16]; 16];
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<Vec<Vec<VarInt>>>(&grid).await?;
+
+        cursor.set_position(0);
+        let mut budget = OpBudget::new(16 + 256);
+        assert_eq!(Grid::decode(&mut budget, &mut cursor).await?, grid);
+        assert_eq!(budget.spent(), 272);
+
+        budget.reset();
+        assert_eq!(budget.spent(), 0);
+
+        cursor.set_position(0);
+        let mut budget = OpBudget::new(100);
+        let error = Grid::decode(&mut budget, &mut cursor).await;
+        assert_matches!(
+            error,
+            Err(TransportError::LimitExceeded(
+                100,
+                101,
+                "decoding budgeted component"
+            ))
+        );
+        assert_eq!(budget.spent(), 100);
+        Ok(())
+    }
+}
@@ -1,8 +1,9 @@
+use crate::delegate::guard::UNTRUSTED_CAPACITY;
 use crate::prelude::{
     DraxReadExt, DraxResult, DraxWriteExt, PacketComponent, Size, TransportError, VarInt,
 };
-use std::collections::HashMap;
-use std::hash::Hash;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
 use std::marker::PhantomData;
 
 impl<C: Send + Sync, K: PacketComponent<C>, V: PacketComponent<C>> PacketComponent<C>
@@ -34,10 +35,60 @@ where
 
     fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
         let mut size = Size::Constant(0);
-        size = size + <VarInt as PacketComponent<C>>::size(&(component_ref.len() as i32), context)?;
+        size = size.checked_add(<VarInt as PacketComponent<C>>::size(
+            &(component_ref.len() as i32),
+            context,
+        )?)?;
         for (k, v) in component_ref.iter() {
-            size = size + <K as PacketComponent<C>>::size(k, context)?;
-            size = size + <V as PacketComponent<C>>::size(v, context)?;
+            size = size.checked_add(<K as PacketComponent<C>>::size(k, context)?)?;
+            size = size.checked_add(<V as PacketComponent<C>>::size(v, context)?)?;
+        }
+        Ok(size)
+    }
+}
+
+/// Entries are laid out exactly like a `HashMap` but are encoded in key order, so equal maps
+/// always encode to the same bytes.
+impl<C: Send + Sync, K: PacketComponent<C>, V: PacketComponent<C>> PacketComponent<C>
+    for BTreeMap<K, V>
+where
+    K::ComponentType: Ord,
+{
+    type ComponentType = BTreeMap<K::ComponentType, V::ComponentType>;
+
+    decode!(read, context {
+        let len = read.read_var_int().await?;
+        if len < 0 {
+            return TransportError::limit_exceeded(0, len, "decoding map");
+        }
+
+        let mut map = BTreeMap::new();
+        for _ in 0..len {
+            map.insert(
+                K::decode(context, read).await?,
+                V::decode(context, read).await?,
+            );
+        }
+        Ok(map)
+    });
+
+    encode!(component_ref, write, context {
+        write.write_var_int(component_ref.len() as i32).await?;
+        for (k, v) in component_ref {
+            K::encode(k, context, write).await?;
+            V::encode(v, context, write).await?;
+        }
+    });
+
+    fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        let mut size = Size::Constant(0);
+        size = size.checked_add(<VarInt as PacketComponent<C>>::size(
+            &(component_ref.len() as i32),
+            context,
+        )?)?;
+        for (k, v) in component_ref.iter() {
+            size = size.checked_add(<K as PacketComponent<C>>::size(k, context)?)?;
+            size = size.checked_add(<V as PacketComponent<C>>::size(v, context)?)?;
         }
         Ok(size)
     }
@@ -87,12 +138,140 @@ where
     }
 }
 
+/// A delegate struct which encodes and decodes a `HashMap` using the hasher built by `S`.
+///
+/// The plain `HashMap<K, V>` implementation always uses `RandomState`, this delegate allows a
+/// faster hasher or a hasher hardened against untrusted keys to be used instead.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::collections::hash_map::DefaultHasher;
+/// # use std::collections::HashMap;
+/// # use std::hash::BuildHasherDefault;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// type FixedState = BuildHasherDefault<DefaultHasher>;
+///
+/// let mut map = HashMap::<i32, i32, FixedState>::default();
+/// map.insert(10, 20);
+///
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<HashMapWith<FixedState, i32, i32>>(&map).await?;
+/// cursor.set_position(0);
+/// let back = cursor.decode_component::<HashMapWith<FixedState, i32, i32>>().await?;
+/// assert_eq!(back, map);
+/// # Ok(())
+/// # }
+/// ```
+pub struct HashMapWith<S, K, V>(PhantomData<(S, K, V)>);
+
+impl<C: Send + Sync, S, K: PacketComponent<C>, V: PacketComponent<C>> PacketComponent<C>
+    for HashMapWith<S, K, V>
+where
+    S: BuildHasher + Default + Send + Sync,
+    K::ComponentType: Eq + Hash,
+{
+    type ComponentType = HashMap<K::ComponentType, V::ComponentType, S>;
+
+    decode!(read, context {
+        let len = read.read_var_int().await?;
+        if len < 0 {
+            return TransportError::limit_exceeded(0, len, "decoding map");
+        }
+
+        // The length is untrusted, the map grows as entries are actually decoded.
+        let capacity = (len as usize).min(UNTRUSTED_CAPACITY);
+        let mut map = HashMap::with_capacity_and_hasher(capacity, S::default());
+        for _ in 0..len {
+            map.insert(
+                K::decode(context, read).await?,
+                V::decode(context, read).await?,
+            );
+        }
+        Ok(map)
+    });
+
+    encode!(component_ref, write, context {
+        write.write_var_int(component_ref.len() as i32).await?;
+        for (k, v) in component_ref {
+            K::encode(k, context, write).await?;
+            V::encode(v, context, write).await?;
+        }
+    });
+
+    fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        let mut size = Size::Constant(0);
+        size = size.checked_add(<VarInt as PacketComponent<C>>::size(
+            &(component_ref.len() as i32),
+            context,
+        )?)?;
+        for (k, v) in component_ref.iter() {
+            size = size.checked_add(<K as PacketComponent<C>>::size(k, context)?)?;
+            size = size.checked_add(<V as PacketComponent<C>>::size(v, context)?)?;
+        }
+        Ok(size)
+    }
+}
+
+/// A delegate struct which encodes and decodes map entries as a `Vec` of pairs, preserving
+/// the order of the entries.
+///
+/// The entries are laid out exactly like a `HashMap` but are neither deduplicated nor
+/// reordered, giving the sender full control over the order and duplicates on the wire.
+pub struct OrderedMap<K, V>(PhantomData<(K, V)>);
+
+impl<C: Send + Sync, K: PacketComponent<C>, V: PacketComponent<C>> PacketComponent<C>
+    for OrderedMap<K, V>
+{
+    type ComponentType = Vec<(K::ComponentType, V::ComponentType)>;
+
+    decode!(read, context {
+        let len = read.read_var_int().await?;
+        if len < 0 {
+            return TransportError::limit_exceeded(0, len, "decoding map");
+        }
+
+        let mut entries = Vec::with_capacity((len as usize).min(UNTRUSTED_CAPACITY));
+        for _ in 0..len {
+            entries.push((
+                K::decode(context, read).await?,
+                V::decode(context, read).await?,
+            ));
+        }
+        Ok(entries)
+    });
+
+    encode!(component_ref, write, context {
+        write.write_var_int(component_ref.len() as i32).await?;
+        for (k, v) in component_ref {
+            K::encode(k, context, write).await?;
+            V::encode(v, context, write).await?;
+        }
+    });
+
+    fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        let mut size = Size::Constant(0);
+        size = size.checked_add(<VarInt as PacketComponent<C>>::size(
+            &(component_ref.len() as i32),
+            context,
+        )?)?;
+        for (k, v) in component_ref.iter() {
+            size = size.checked_add(<K as PacketComponent<C>>::size(k, context)?)?;
+            size = size.checked_add(<V as PacketComponent<C>>::size(v, context)?)?;
+        }
+        Ok(size)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::delegate::map::LimitedMap;
-    use crate::prelude::{DraxReadExt, DraxResult, DraxWriteExt, TransportError};
+    use crate::delegate::map::{HashMapWith, LimitedMap, OrderedMap};
+    use crate::prelude::{
+        DraxReadExt, DraxResult, DraxWriteExt, PacketComponent, Size, TransportError,
+    };
     use std::assert_matches::assert_matches;
-    use std::collections::HashMap;
+    use std::collections::{BTreeMap, HashMap};
+    use std::hash::{BuildHasherDefault, Hasher};
     use std::io::Cursor;
 
     #[tokio::test]
@@ -128,6 +307,19 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    pub async fn test_btree_map_encodes_in_key_order() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        let in_map = BTreeMap::from([(3u8, 30u8), (1, 10), (2, 20)]);
+        cursor.encode_own_component(&in_map).await?;
+        assert_eq!(cursor.get_ref(), &vec![3, 1, 10, 2, 20, 3, 30]);
+
+        cursor.set_position(0);
+        let out_map = cursor.decode_own_component::<BTreeMap<_, _>>().await?;
+        assert_eq!(out_map, in_map);
+        Ok(())
+    }
+
     #[tokio::test]
     pub async fn test_limited_map_encoding_failure() -> DraxResult<()> {
         let mut cursor = Cursor::new(vec![]);
@@ -143,4 +335,112 @@ mod test {
         );
         Ok(())
     }
+
+    #[tokio::test]
+    pub async fn test_ordered_map_preserves_order() -> DraxResult<()> {
+        let entries = vec![
+            ("zeta".to_string(), 1),
+            ("alpha".to_string(), 2),
+            ("zeta".to_string(), 3),
+        ];
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<OrderedMap<String, i32>>(&entries)
+            .await?;
+        assert_eq!(
+            OrderedMap::<String, i32>::size(&entries, &mut ())?,
+            Size::Dynamic(cursor.get_ref().len())
+        );
+        cursor.set_position(0);
+
+        let back = cursor.decode_component::<OrderedMap<String, i32>>().await?;
+        assert_eq!(back, entries);
+        Ok(())
+    }
+
+    #[derive(Default)]
+    struct FnvHasher(u64);
+
+    impl Hasher for FnvHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            if self.0 == 0 {
+                self.0 = 0xcbf29ce484222325;
+            }
+            for byte in bytes {
+                self.0 ^= *byte as u64;
+                self.0 = self.0.wrapping_mul(0x100000001b3);
+            }
+        }
+    }
+
+    type FnvState = BuildHasherDefault<FnvHasher>;
+    type FnvMap = HashMapWith<FnvState, String, i32>;
+
+    #[tokio::test]
+    pub async fn test_map_with_custom_hasher() -> DraxResult<()> {
+        let mut in_map = HashMap::<String, i32, FnvState>::default();
+        in_map.insert("example1".to_string(), 20);
+        in_map.insert("example2".to_string(), 40);
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<FnvMap>(&in_map).await?;
+        assert_eq!(
+            FnvMap::size(&in_map, &mut ())?,
+            HashMap::<String, i32>::size(&HashMap::from_iter(in_map.clone()), &mut ())?
+        );
+        cursor.set_position(0);
+
+        let out_map = cursor.decode_component::<FnvMap>().await?;
+
+        assert_eq!(out_map, in_map);
+        assert_eq!(out_map.get("example2"), Some(&40));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_map_with_untrusted_length() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(i32::MAX).await?;
+        cursor.set_position(0);
+        let error = cursor.decode_component::<FnvMap>().await;
+        assert!(error.is_err_and(|e| e.is_eof()));
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(-1).await?;
+        cursor.set_position(0);
+        let error = cursor.decode_component::<FnvMap>().await;
+        assert_matches!(
+            error,
+            Err(TransportError::LimitExceeded(0, -1, "decoding map"))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_ordered_map_untrusted_length() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(i32::MAX).await?;
+        cursor.set_position(0);
+        let error = cursor
+            .decode_component::<OrderedMap<String, String>>()
+            .await;
+        assert!(error.is_err_and(|e| e.is_eof()));
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(-1).await?;
+        cursor.set_position(0);
+        let error = cursor
+            .decode_component::<OrderedMap<String, String>>()
+            .await;
+        assert_matches!(
+            error,
+            Err(TransportError::LimitExceeded(0, -1, "decoding map"))
+        );
+        Ok(())
+    }
 }
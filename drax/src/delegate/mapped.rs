@@ -0,0 +1,345 @@
+use crate::prelude::{DraxResult, PacketComponent, Size};
+use std::marker::PhantomData;
+
+/// Defines a conversion between the wire representation of a field and its domain type.
+///
+/// Codecs are implemented on a zero sized type and used through [`Mapped`], which lets a field
+/// apply a bespoke transform without a full `PacketComponent` implementation.
+pub trait Codec<Wire, Domain> {
+    /// Converts the decoded wire value into the domain type.
+    fn from_wire(wire: Wire) -> DraxResult<Domain>;
+
+    /// Converts the domain value into the wire value to be encoded.
+    fn to_wire(domain: &Domain) -> DraxResult<Wire>;
+}
+
+/// A delegate struct which encodes and decodes a `D` through the wire component `T`, converting
+/// between the two with the codec `F`.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// #[derive(Debug, PartialEq)]
+/// enum Hand {
+///     Main,
+///     Off,
+/// }
+///
+/// struct HandCodec;
+///
+/// impl Codec<i32, Hand> for HandCodec {
+///     fn from_wire(wire: i32) -> DraxResult<Hand> {
+///         match wire {
+///             0 => Ok(Hand::Main),
+///             1 => Ok(Hand::Off),
+///             other => TransportError::invalid_variant(other),
+///         }
+///     }
+///
+///     fn to_wire(domain: &Hand) -> DraxResult<i32> {
+///         Ok(match domain {
+///             Hand::Main => 0,
+///             Hand::Off => 1,
+///         })
+///     }
+/// }
+///
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<Mapped<VarInt, HandCodec, Hand>>(&Hand::Off).await?;
+/// cursor.set_position(0);
+/// let back = cursor.decode_component::<Mapped<VarInt, HandCodec, Hand>>().await?;
+/// assert_eq!(back, Hand::Off);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Mapped<T, F, D>(PhantomData<(T, F, D)>);
+
+impl<C: Send + Sync, T, F, D> PacketComponent<C> for Mapped<T, F, D>
+where
+    T: PacketComponent<C>,
+    F: Codec<T::ComponentType, D>,
+    D: Send + Sync,
+{
+    type ComponentType = D;
+
+    decode!(read, context {
+        F::from_wire(T::decode(context, read).await?)
+    });
+
+    encode!(component_ref, write, context {
+        T::encode(&F::to_wire(component_ref)?, context, write).await?
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        T::size(&F::to_wire(input)?, context)
+    }
+}
+
+/// Defines a domain type which wraps a single wire value, such as `struct EntityId(i32)`.
+///
+/// Newtypes are encoded and decoded through [`Transparent`] exactly like the value they wrap,
+/// giving the value its own type identity without a `PacketComponent` implementation.
+pub trait Newtype {
+    type Inner;
+
+    fn from_inner(inner: Self::Inner) -> Self;
+
+    fn as_inner(&self) -> &Self::Inner;
+}
+
+/// A delegate struct which encodes and decodes the newtype `D` as the wire component `T` it
+/// wraps.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// #[derive(Debug, PartialEq)]
+/// struct EntityId(i32);
+///
+/// impl Newtype for EntityId {
+///     type Inner = i32;
+///
+///     fn from_inner(inner: i32) -> Self {
+///         EntityId(inner)
+///     }
+///
+///     fn as_inner(&self) -> &i32 {
+///         &self.0
+///     }
+/// }
+///
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<Transparent<VarInt, EntityId>>(&EntityId(300)).await?;
+/// assert_eq!(cursor.get_ref(), &vec![172, 2]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Transparent<T, D>(PhantomData<(T, D)>);
+
+impl<C: Send + Sync, T, D> PacketComponent<C> for Transparent<T, D>
+where
+    T: PacketComponent<C>,
+    D: Newtype<Inner = T::ComponentType> + Send + Sync,
+{
+    type ComponentType = D;
+
+    const FIXED_SIZE: Option<usize> = T::FIXED_SIZE;
+
+    decode!(read, context {
+        Ok(D::from_inner(T::decode(context, read).await?))
+    });
+
+    encode!(component_ref, write, context {
+        T::encode(component_ref.as_inner(), context, write).await?
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        T::size(input.as_inner(), context)
+    }
+}
+
+/// Defines a semantic check on a decoded value, such as an id having to be known to the
+/// context.
+///
+/// Predicates are implemented on a zero sized type and used through [`Checked`], a rejected value
+/// is reported through the returned error, typically `TransportError::Rejected`.
+pub trait Predicate<T, C> {
+    fn check(value: &T, context: &C) -> DraxResult<()>;
+}
+
+/// A delegate struct which encodes and decodes a `T`, validating the value against the
+/// predicate `P` after decoding and before encoding.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// struct Positive;
+///
+/// impl<C> Predicate<i32, C> for Positive {
+///     fn check(value: &i32, _: &C) -> DraxResult<()> {
+///         match *value > 0 {
+///             true => Ok(()),
+///             false => TransportError::rejected(format!("{value} is not positive")),
+///         }
+///     }
+/// }
+///
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![0]);
+/// let error = cursor.decode_component::<Checked<VarInt, Positive>>().await;
+/// assert!(matches!(error, Err(TransportError::Rejected(_))));
+/// # Ok(())
+/// # }
+/// ```
+pub struct Checked<T, P>(PhantomData<(T, P)>);
+
+impl<C: Send + Sync, T, P> PacketComponent<C> for Checked<T, P>
+where
+    T: PacketComponent<C>,
+    P: Predicate<T::ComponentType, C>,
+{
+    type ComponentType = T::ComponentType;
+
+    const FIXED_SIZE: Option<usize> = T::FIXED_SIZE;
+
+    decode!(read, context {
+        let value = T::decode(context, read).await?;
+        P::check(&value, context)?;
+        Ok(value)
+    });
+
+    encode!(component_ref, write, context {
+        P::check(component_ref, context)?;
+        T::encode(component_ref, context, write).await?
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        T::size(input, context)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{
+        Checked, Codec, DraxReadExt, DraxResult, DraxWriteExt, Mapped, Newtype, PacketComponent,
+        Predicate, Size, Transparent, TransportError, VarInt,
+    };
+    use std::assert_matches::assert_matches;
+    use std::collections::HashSet;
+    use std::io::Cursor;
+
+    #[derive(Debug, Eq, PartialEq)]
+    enum GameMode {
+        Survival,
+        Creative,
+        Spectator,
+    }
+
+    struct GameModeCodec;
+
+    impl Codec<i32, GameMode> for GameModeCodec {
+        fn from_wire(wire: i32) -> DraxResult<GameMode> {
+            match wire {
+                0 => Ok(GameMode::Survival),
+                1 => Ok(GameMode::Creative),
+                3 => Ok(GameMode::Spectator),
+                other => TransportError::invalid_variant(other),
+            }
+        }
+
+        fn to_wire(domain: &GameMode) -> DraxResult<i32> {
+            Ok(match domain {
+                GameMode::Survival => 0,
+                GameMode::Creative => 1,
+                GameMode::Spectator => 3,
+            })
+        }
+    }
+
+    type MappedGameMode = Mapped<VarInt, GameModeCodec, GameMode>;
+
+    #[tokio::test]
+    pub async fn test_mapped_round_trip() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<MappedGameMode>(&GameMode::Spectator)
+            .await?;
+        assert_eq!(cursor.get_ref(), &vec![3]);
+        assert_eq!(
+            MappedGameMode::size(&GameMode::Spectator, &mut ())?,
+            Size::Dynamic(1)
+        );
+        cursor.set_position(0);
+
+        let back = cursor.decode_component::<MappedGameMode>().await?;
+        assert_eq!(back, GameMode::Spectator);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_mapped_invalid_wire_value() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![2]);
+
+        let error = cursor.decode_component::<MappedGameMode>().await;
+
+        assert_matches!(error, Err(TransportError::InvalidVariant(2)));
+        Ok(())
+    }
+
+    #[derive(Debug, Eq, PartialEq)]
+    struct EntityId(i32);
+
+    impl Newtype for EntityId {
+        type Inner = i32;
+
+        fn from_inner(inner: i32) -> Self {
+            EntityId(inner)
+        }
+
+        fn as_inner(&self) -> &i32 {
+            &self.0
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_transparent_newtype() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<Transparent<VarInt, EntityId>>(&EntityId(300))
+            .await?;
+        assert_eq!(cursor.get_ref(), &vec![172, 2]);
+        assert_eq!(
+            Transparent::<VarInt, EntityId>::size(&EntityId(300), &mut ())?,
+            Size::Dynamic(2)
+        );
+        assert_eq!(
+            <Transparent<i32, EntityId> as PacketComponent<()>>::FIXED_SIZE,
+            Some(4)
+        );
+
+        cursor.set_position(0);
+        let back = cursor
+            .decode_component::<Transparent<VarInt, EntityId>>()
+            .await?;
+        assert_eq!(back, EntityId(300));
+        Ok(())
+    }
+
+    struct KnownIds {
+        allowed: HashSet<i32>,
+    }
+
+    struct IsKnownId;
+
+    impl Predicate<i32, KnownIds> for IsKnownId {
+        fn check(value: &i32, context: &KnownIds) -> DraxResult<()> {
+            match context.allowed.contains(value) {
+                true => Ok(()),
+                false => TransportError::rejected(format!("unknown id {value}")),
+            }
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_checked_allowlist() -> DraxResult<()> {
+        let mut known = KnownIds {
+            allowed: HashSet::from([1, 300]),
+        };
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(300).await?;
+        cursor.write_var_int(2).await?;
+        cursor.set_position(0);
+
+        let back = Checked::<VarInt, IsKnownId>::decode(&mut known, &mut cursor).await?;
+        assert_eq!(back, 300);
+
+        let error = Checked::<VarInt, IsKnownId>::decode(&mut known, &mut cursor).await;
+        assert_matches!(error, Err(TransportError::Rejected(ref reason)) if reason == "unknown id 2");
+
+        let error = Checked::<VarInt, IsKnownId>::encode(&2, &mut known, &mut vec![]).await;
+        assert_matches!(error, Err(TransportError::Rejected(_)));
+        Ok(())
+    }
+}
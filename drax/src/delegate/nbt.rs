@@ -1,5 +1,6 @@
-use crate::prelude::{DraxResult, NbtError, PacketComponent, Size};
-use std::io::Cursor;
+use crate::io::CountingReader;
+use crate::prelude::{DraxReadExt, DraxResult, NbtError, PacketComponent, Size, TransportError};
+use crate::transport::{return_scratch, take_scratch};
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 
 pub const COMPOUND_TAG_BIT: u8 = 10;
@@ -10,6 +11,12 @@ pub struct NbtAccounter {
 }
 
 impl NbtAccounter {
+    /// Creates an accounter which fails once more than `limit` bytes are accounted, a limit of
+    /// zero disables accounting.
+    pub fn new(limit: u64) -> Self {
+        Self { limit, current: 0 }
+    }
+
     pub fn account_bytes(&mut self, bytes: u64) -> DraxResult<()> {
         if self.limit == 0 {
             return Ok(());
@@ -63,8 +70,8 @@ macro_rules! define_tags {
             }
         }
 
-        pub async fn load_tag<R: ::tokio::io::AsyncRead + Unpin + Send + Sync + ?Sized>(
-            read: &mut R,
+        async fn load_tag_at<R: ::tokio::io::AsyncRead + Unpin + Send + Sync + ?Sized>(
+            read: &mut CountingReader<&mut R>,
             bit: u8,
             depth: i32,
             accounter: &mut $crate::delegate::nbt::NbtAccounter
@@ -108,16 +115,44 @@ macro_rules! define_tags {
     };
 }
 
+/// Loads a tag of the given type from the reader.
+///
+/// Nbt errors raised while reading nested tags are reported as `NbtError::At`, carrying the
+/// path to the failing tag and the offset into the reader at which it was detected.
+pub async fn load_tag<R: AsyncRead + Unpin + Send + Sync + ?Sized>(
+    read: &mut R,
+    bit: u8,
+    depth: i32,
+    accounter: &mut NbtAccounter,
+) -> DraxResult<Tag> {
+    load_tag_at(&mut CountingReader::new(read), bit, depth, accounter).await
+}
+
+/// Encodes a string as Java's modified CESU-8, the string encoding used by nbt.
+///
+/// The returned bytes are not length prefixed.
+pub fn encode_cesu8(value: &str) -> Vec<u8> {
+    cesu8::to_java_cesu8(value).into_owned()
+}
+
+/// Decodes a Java modified CESU-8 byte sequence, the string encoding used by nbt.
+///
+/// The given bytes are expected to not be length prefixed.
+pub fn decode_cesu8(bytes: &[u8]) -> DraxResult<String> {
+    Ok(cesu8::from_java_cesu8(bytes)
+        .map_err(NbtError::from)?
+        .into_owned())
+}
+
 async fn read_string<R: AsyncRead + Unpin + Send + Sync + ?Sized>(
     read: &mut R,
     accounter: &mut NbtAccounter,
 ) -> DraxResult<String> {
     let len = read.read_u16().await?;
-    let mut bytes = vec![0u8; len as usize];
-    read.read_exact(&mut bytes).await?;
-    let string = cesu8::from_java_cesu8(&bytes)
-        .map_err(NbtError::from)?
-        .to_string();
+    let bytes = read
+        .read_exact_limited(len as usize, u16::MAX as usize)
+        .await?;
+    let string = decode_cesu8(&bytes)?;
     accounter.account_bytes(string.len() as u64)?;
     Ok(string)
 }
@@ -249,8 +284,12 @@ define_tags! {
             accounter.account_bytes(24)?;
             let len = reader.read_i32().await?;
             accounter.account_bytes(len as u64)?;
-            let mut bytes = vec![0u8; len as usize];
-            reader.read_exact(&mut bytes).await?;
+            if len < 0 {
+                return TransportError::limit_exceeded(0, len, "decoding byte array tag");
+            }
+            let bytes = reader
+                .read_exact_limited(len as usize, i32::MAX as usize)
+                .await?;
             Ok(Tag::TagByteArray(bytes))
         },
     },
@@ -295,8 +334,13 @@ define_tags! {
             let length = reader.read_i32().await?;
             accounter.account_bytes((4 * length) as u64)?;
             let mut v = Vec::with_capacity(length as usize);
-            for _ in 0..length {
-                v.push(Box::pin(load_tag(reader, tag_byte, depth + 1, accounter)).await?);
+            for index in 0..length {
+                match Box::pin(load_tag_at(reader, tag_byte, depth + 1, accounter)).await {
+                    Ok(tag) => v.push(tag),
+                    Err(error) => {
+                        return Err(NbtError::at(&format!("[{index}]"), reader.count(), error));
+                    }
+                }
             }
             Ok(Tag::TagList((tag_byte, v)))
         },
@@ -341,7 +385,11 @@ define_tags! {
                 }
                 accounter.account_bytes(28)?;
                 let key = read_string(reader, accounter).await?;
-                let data = Box::pin(load_tag(reader, tag_byte, depth + 1, accounter)).await?;
+                let data = Box::pin(load_tag_at(reader, tag_byte, depth + 1, accounter)).await;
+                let data = match data {
+                    Ok(data) => data,
+                    Err(error) => return Err(NbtError::at(&key, reader.count(), error)),
+                };
                 map.push((key, data));
                 accounter.account_bytes(36)?;
             }
@@ -398,8 +446,13 @@ define_tags! {
 
 #[cfg(test)]
 mod test {
-    use crate::delegate::nbt::{load_tag, read_string, write_string, write_tag, NbtAccounter, Tag};
-    use crate::prelude::DraxResult;
+    use crate::delegate::nbt::{
+        decode_cesu8, encode_cesu8, load_tag, read_string, write_string, write_tag,
+        EnsuredCompoundTag, EnsuredTag, NbtAccounter, Tag,
+    };
+    use crate::prelude::{DraxReadExt, DraxResult, DraxWriteExt, NbtError, TransportError};
+    use crate::transport::{return_scratch, take_scratch};
+    use std::assert_matches::assert_matches;
     use std::io::Cursor;
 
     pub async fn __test_io(value: Tag) -> DraxResult<()> {
@@ -452,6 +505,148 @@ mod test {
         test_tag_long_array, Tag::TagLongArray(vec![321423, 24312, 123123, 12312])
     }
 
+    #[tokio::test]
+    pub async fn test_ensured_compound_tag_reuses_scratch() -> DraxResult<()> {
+        let root = Some(Tag::compound_tag(vec![
+            ("name", Tag::string("scratch")),
+            ("values", Tag::TagIntArray(vec![1, 2, 3, 4])),
+        ]));
+
+        let mut first = Cursor::new(vec![]);
+        first.encode_component::<EnsuredCompoundTag>(&root).await?;
+        let scratch = take_scratch();
+        let capacity = scratch.capacity();
+        assert!(scratch.is_empty());
+        assert!(capacity >= first.get_ref().len());
+        return_scratch(scratch);
+
+        for _ in 0..3 {
+            let mut cursor = Cursor::new(vec![]);
+            cursor.encode_component::<EnsuredCompoundTag>(&root).await?;
+            assert_eq!(cursor.get_ref(), first.get_ref());
+            cursor.set_position(0);
+            assert_eq!(cursor.decode_component::<EnsuredCompoundTag>().await?, root);
+        }
+        assert_eq!(take_scratch().capacity(), capacity);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_ensured_compound_tag_decode_exact() -> DraxResult<()> {
+        let root = Some(Tag::compound_tag(vec![("level", Tag::TagInt(7))]));
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<EnsuredCompoundTag>(&root).await?;
+        let exact = cursor.get_ref().clone();
+        cursor.get_mut().extend_from_slice(&[0xDE, 0xAD, 0xBE]);
+
+        cursor.set_position(0);
+        assert_eq!(cursor.decode_component::<EnsuredCompoundTag>().await?, root);
+        cursor.set_position(0);
+        assert_matches!(
+            EnsuredCompoundTag::<0>::decode_exact(&mut cursor).await,
+            Err(TransportError::TrailingBytes(3))
+        );
+
+        assert_eq!(
+            EnsuredCompoundTag::<0>::decode_exact(&mut Cursor::new(exact)).await?,
+            root
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_ensured_tag_root_list() -> DraxResult<()> {
+        let root = Some((
+            "list".to_string(),
+            Tag::TagList((3, vec![Tag::TagInt(10), Tag::TagInt(20)])),
+        ));
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<EnsuredTag>(&root).await?;
+        cursor.set_position(0);
+        assert_eq!(cursor.decode_component::<EnsuredTag>().await?, root);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_ensured_tag_root_int_array() -> DraxResult<()> {
+        let root = Some((String::new(), Tag::TagIntArray(vec![30, 23, 123, 955])));
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<EnsuredTag>(&root).await?;
+        assert_eq!(cursor.get_ref()[0], 11);
+        cursor.set_position(0);
+        assert_eq!(cursor.decode_component::<EnsuredTag>().await?, root);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_nested_error_path() -> DraxResult<()> {
+        let section = |y| Tag::compound_tag(vec![("Y", Tag::TagByte(y))]);
+        let root = Tag::compound_tag(vec![(
+            "Level",
+            Tag::compound_tag(vec![(
+                "Sections",
+                Tag::TagList((10, vec![section(0), section(1), section(2)])),
+            )]),
+        )]);
+
+        let mut cursor = Cursor::new(vec![]);
+        write_tag(&mut cursor, &root).await?;
+        let mut bytes = cursor.into_inner();
+
+        let key = [1, 0, 1, b'Y'];
+        let positions: Vec<usize> = bytes
+            .windows(key.len())
+            .enumerate()
+            .filter(|(_, window)| *window == key)
+            .map(|(position, _)| position)
+            .collect();
+        bytes[positions[2]] = 57;
+
+        let error = load_tag(
+            &mut Cursor::new(bytes),
+            root.get_tag_bit(),
+            0,
+            &mut NbtAccounter {
+                limit: 0,
+                current: 0,
+            },
+        )
+        .await;
+
+        match error {
+            Err(TransportError::NbtError(NbtError::At {
+                path,
+                offset,
+                source,
+            })) => {
+                assert_eq!(path, "Level -> Sections -> [2] -> Y");
+                assert_eq!(offset, (positions[2] + key.len()) as u64);
+                assert_matches!(*source, NbtError::InvalidTagBit(57));
+            }
+            other => panic!("Unexpected result {other:?}"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_cesu8_supplementary_plane() -> DraxResult<()> {
+        let value = "grin \u{1F600}";
+        let encoded = encode_cesu8(value);
+        assert_eq!(encoded.len(), 5 + 6);
+        assert_ne!(encoded.as_slice(), value.as_bytes());
+        assert_eq!(decode_cesu8(&encoded)?, value);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_cesu8_rejects_invalid_bytes() {
+        let error = decode_cesu8(&[b'a', 0xFF, b'b']);
+        assert_matches!(
+            error,
+            Err(TransportError::NbtError(NbtError::Cesu8DecodingError(_)))
+        );
+    }
+
     #[tokio::test]
     pub async fn test_string_read_write_persistence() -> DraxResult<()> {
         let ref_string = "Example String".to_string();
@@ -521,6 +716,7 @@ impl<const LIMIT: u64, C: Send + Sync> PacketComponent<C> for EnsuredCompoundTag
     type ComponentType = Option<Tag>;
 
     decode!(read {
+        let mut read = CountingReader::new(read);
         let b = read.read_u8().await?;
         if b == 0 {
             return Ok(None);
@@ -528,28 +724,24 @@ impl<const LIMIT: u64, C: Send + Sync> PacketComponent<C> for EnsuredCompoundTag
         if b != 10 {
             return NbtError::invalid_tag_bit(b);
         }
-        let mut accounter = NbtAccounter {
-            limit: LIMIT,
-            current: 0,
-        };
-        let _ = read_string(read, &mut accounter).await?;
-        let tag = load_tag(read, b, 0, &mut accounter).await?;
+        let mut accounter = NbtAccounter::new(LIMIT);
+        let _ = read_string(&mut read, &mut accounter).await?;
+        let tag = load_tag_at(&mut read, b, 0, &mut accounter).await?;
         Ok(Some(tag))
     });
 
     encode!(component_ref, write {
-        let mut buffer = Cursor::new(Vec::with_capacity(
-            match Self::size(component_ref, &mut ())? {
-                Size::Dynamic(x) | Size::Constant(x) => x,
-            },
-        ));
         match component_ref {
             Some(tag) => {
+                // The tag is serialized into the thread's scratch buffer so repeated encodes
+                // reuse one allocation instead of allocating a buffer per call.
+                let mut buffer = take_scratch();
+                buffer.reserve(Self::size(component_ref, &mut ())?.as_usize());
                 buffer.write_u8(10).await?;
                 write_string(&mut buffer, "").await?;
                 write_tag(&mut buffer, tag).await?;
-                let inner = buffer.into_inner();
-                write.write_all(&inner).await?;
+                write.write_all(&buffer).await?;
+                return_scratch(buffer);
             }
             None => {
                 write.write_u8(0).await?;
@@ -567,3 +759,63 @@ impl<const LIMIT: u64, C: Send + Sync> PacketComponent<C> for EnsuredCompoundTag
         }
     }
 }
+
+impl<const LIMIT: u64> EnsuredCompoundTag<LIMIT> {
+    /// Decodes the root compound and requires the reader to be exhausted afterwards.
+    ///
+    /// This is meant for readers holding exactly one compound such as an nbt file, where bytes
+    /// after the root indicate a corrupt or concatenated file. The trailing bytes are drained and
+    /// reported as a `TransportError::TrailingBytes`.
+    pub async fn decode_exact<R: AsyncRead + Unpin + Send + Sync + ?Sized>(
+        read: &mut R,
+    ) -> DraxResult<Option<Tag>> {
+        let tag = <Self as PacketComponent<()>>::decode(&mut (), read).await?;
+        let trailing = tokio::io::copy(read, &mut tokio::io::sink()).await?;
+        if trailing != 0 {
+            return TransportError::trailing_bytes(trailing);
+        }
+        Ok(tag)
+    }
+}
+
+/// A delegate struct which encodes and decodes a named root tag of any type.
+///
+/// Unlike `EnsuredCompoundTag` the root is not required to be a compound, the leading type
+/// byte selects the tag which is loaded. A leading `TagEnd` is decoded as `None`.
+pub struct EnsuredTag<const LIMIT: u64 = 0>;
+
+impl<const LIMIT: u64, C: Send + Sync> PacketComponent<C> for EnsuredTag<LIMIT> {
+    type ComponentType = Option<(String, Tag)>;
+
+    decode!(read {
+        let mut read = CountingReader::new(read);
+        let b = read.read_u8().await?;
+        if b == 0 {
+            return Ok(None);
+        }
+        let mut accounter = NbtAccounter::new(LIMIT);
+        let name = read_string(&mut read, &mut accounter).await?;
+        let tag = load_tag_at(&mut read, b, 0, &mut accounter).await?;
+        Ok(Some((name, tag)))
+    });
+
+    encode!(component_ref, write {
+        match component_ref {
+            Some((name, tag)) => {
+                write.write_u8(tag.get_tag_bit()).await?;
+                write_string(write, name).await?;
+                write_tag(write, tag).await?;
+            }
+            None => {
+                write.write_u8(0).await?;
+            }
+        }
+    });
+
+    fn size(input: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+        match input {
+            Some((name, tag)) => Ok(Size::Dynamic(1 + size_string(name)? + size_tag(tag)?)),
+            None => Ok(Size::Constant(1)),
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use crate::prelude::{DraxResult, PacketComponent, Size};
+use crate::prelude::{DraxResult, PacketComponent, Size, TransportError};
+use std::marker::PhantomData;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 /// A delegate struct which encodes and decodes an `Option<T>` type.
@@ -57,10 +58,244 @@ impl<C: Send + Sync, T: PacketComponent<C>> PacketComponent<C> for Maybe<T> {
     });
 
     fn size(input: &Self::ComponentType, ctx: &mut C) -> DraxResult<Size> {
-        Ok(if let Some(value) = input {
-            Size::Constant(1) + T::size(value, ctx)?
+        if let Some(value) = input {
+            Size::Constant(1).checked_add(T::size(value, ctx)?)
         } else {
-            Size::Constant(1)
+            Ok(Size::Constant(1))
+        }
+    }
+}
+
+/// A delegate struct which encodes and decodes an `Option` of an integer by reserving a sentinel
+/// value to represent `None`, rather than writing a presence byte.
+///
+/// `None` is encoded as `SENTINEL` and any decoded value equal to `SENTINEL` is returned as
+/// `None`. Encoding `Some(SENTINEL)` fails with a `TransportError::SentinelCollision`, since it
+/// could not be told apart from `None`. Any integer delegate whose component type can represent
+/// every `i32` can be used, such as `VarInt`, `i32`, `VarLong` or `i64`.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<SentinelOption<VarInt, 0>>(&None).await?;
+/// cursor.encode_component::<SentinelOption<VarInt, 0>>(&Some(10)).await?;
+/// assert_eq!(cursor.get_ref(), &vec![0, 10]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct SentinelOption<T, const SENTINEL: i32>(PhantomData<T>);
+
+impl<C: Send + Sync, T, const SENTINEL: i32> PacketComponent<C> for SentinelOption<T, SENTINEL>
+where
+    T: PacketComponent<C>,
+    T::ComponentType: From<i32> + PartialEq + Copy,
+{
+    type ComponentType = Option<T::ComponentType>;
+
+    const FIXED_SIZE: Option<usize> = T::FIXED_SIZE;
+
+    decode!(read, context {
+        let value = T::decode(context, read).await?;
+        Ok(if value == T::ComponentType::from(SENTINEL) {
+            None
+        } else {
+            Some(value)
         })
+    });
+
+    encode!(component_ref, write, context {
+        let sentinel = T::ComponentType::from(SENTINEL);
+        match component_ref {
+            None => T::encode(&sentinel, context, write).await?,
+            Some(value) if *value == sentinel => {
+                return TransportError::sentinel_collision(SENTINEL);
+            }
+            Some(value) => T::encode(value, context, write).await?,
+        }
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        match input {
+            None => T::size(&T::ComponentType::from(SENTINEL), context),
+            Some(value) => T::size(value, context),
+        }
+    }
+}
+
+/// A delegate struct which encodes and decodes a group of optional fields behind a bitmask byte
+/// declaring which of them are present.
+///
+/// The fields are declared as a tuple of up to eight components, `BitmaskFields<(T0, T1, ..)>`
+/// has the component type `(Option<T0::ComponentType>, Option<T1::ComponentType>, ..)`. Bit `i`
+/// of the mask is set if field `i` is `Some`, the present fields follow the mask in order. A
+/// decoded mask with bits set past the last field fails with `TransportError::LimitExceeded`.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor
+///     .encode_component::<BitmaskFields<(VarInt, bool, String)>>(&(None, Some(true), None))
+///     .await?;
+/// assert_eq!(cursor.get_ref(), &vec![0b010, 1]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct BitmaskFields<T>(PhantomData<T>);
+
+macro_rules! define_bitmask_bind {
+    ($(($($field:ident $index:tt),+)),*) => {$(
+        impl<C: Send + Sync, $($field),+> PacketComponent<C> for BitmaskFields<($($field,)+)>
+        where
+            $($field: PacketComponent<C>),+
+        {
+            type ComponentType = ($(Option<$field::ComponentType>,)+);
+
+            decode!(read, context {
+                const FIELDS: u32 = [$($index),+].len() as u32;
+                let mask = read.read_u8().await?;
+                if (mask as u32) >> FIELDS != 0 {
+                    return TransportError::limit_exceeded(
+                        (1 << FIELDS) - 1,
+                        mask as i32,
+                        "decoding bitmask fields",
+                    );
+                }
+                Ok(($(
+                    if mask & (1 << $index) != 0 {
+                        Some($field::decode(context, read).await?)
+                    } else {
+                        None
+                    },
+                )+))
+            });
+
+            encode!(component_ref, write, context {
+                let mut mask = 0u8;
+                $(
+                    if component_ref.$index.is_some() {
+                        mask |= 1 << $index;
+                    }
+                )+
+                write.write_u8(mask).await?;
+                $(
+                    if let Some(value) = &component_ref.$index {
+                        $field::encode(value, context, write).await?;
+                    }
+                )+
+            });
+
+            fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+                let size = Size::Dynamic(1);
+                $(
+                    let size = match &input.$index {
+                        Some(value) => size.checked_add($field::size(value, context)?)?,
+                        None => size,
+                    };
+                )+
+                Ok(size)
+            }
+        }
+    )*};
+}
+
+define_bitmask_bind!(
+    (T0 0),
+    (T0 0, T1 1),
+    (T0 0, T1 1, T2 2),
+    (T0 0, T1 1, T2 2, T3 3),
+    (T0 0, T1 1, T2 2, T3 3, T4 4),
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5),
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6),
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7)
+);
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{
+        BitmaskFields, DraxReadExt, DraxResult, DraxWriteExt, PacketComponent, SentinelOption,
+        Size, TransportError, VarInt,
+    };
+    use std::assert_matches::assert_matches;
+    use std::io::Cursor;
+
+    type OptionalSlot = SentinelOption<VarInt, -1>;
+
+    #[tokio::test]
+    pub async fn test_sentinel_option_none() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<OptionalSlot>(&None).await?;
+
+        let mut expected = Cursor::new(vec![]);
+        expected.write_var_int(-1).await?;
+        assert_eq!(cursor.get_ref(), expected.get_ref());
+
+        cursor.set_position(0);
+        assert_eq!(cursor.decode_component::<OptionalSlot>().await?, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_sentinel_option_value() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<OptionalSlot>(&Some(300)).await?;
+        cursor
+            .encode_component::<SentinelOption<i64, { i32::MIN }>>(&Some(-1))
+            .await?;
+        cursor.set_position(0);
+
+        assert_eq!(cursor.decode_component::<OptionalSlot>().await?, Some(300));
+        assert_eq!(
+            cursor
+                .decode_component::<SentinelOption<i64, { i32::MIN }>>()
+                .await?,
+            Some(-1)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_sentinel_option_collision() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        let error = cursor.encode_component::<OptionalSlot>(&Some(-1)).await;
+
+        assert_matches!(error, Err(TransportError::SentinelCollision(-1)));
+        assert!(cursor.get_ref().is_empty());
+        Ok(())
+    }
+
+    type EntityUpdate = BitmaskFields<(VarInt, bool, String, i64)>;
+
+    #[tokio::test]
+    pub async fn test_bitmask_fields() -> DraxResult<()> {
+        let update = (Some(300), None, Some("name".to_string()), None);
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<EntityUpdate>(&update).await?;
+        assert_eq!(
+            cursor.get_ref(),
+            &vec![0b0101, 172, 2, 4, b'n', b'a', b'm', b'e']
+        );
+        assert_eq!(EntityUpdate::size(&update, &mut ())?, Size::Dynamic(8));
+
+        let empty = (None, None, None, None);
+        cursor.encode_component::<EntityUpdate>(&empty).await?;
+        let full = (Some(1), Some(true), Some(String::new()), Some(-1));
+        cursor.encode_component::<EntityUpdate>(&full).await?;
+
+        cursor.set_position(0);
+        assert_eq!(cursor.decode_component::<EntityUpdate>().await?, update);
+        assert_eq!(cursor.decode_component::<EntityUpdate>().await?, empty);
+        assert_eq!(cursor.decode_component::<EntityUpdate>().await?, full);
+
+        let mut bytes: &[u8] = &[0b10000];
+        let error = bytes.decode_component::<EntityUpdate>().await;
+        assert_matches!(
+            error,
+            Err(TransportError::LimitExceeded(0b1111, 0b10000, _))
+        );
+        Ok(())
     }
 }
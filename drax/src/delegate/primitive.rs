@@ -1,7 +1,11 @@
+use std::marker::PhantomData;
 use std::mem::size_of;
 
-use crate::prelude::{DraxReadExt, DraxResult, DraxWriteExt, PacketComponent, Size};
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use crate::prelude::{
+    DecodeSlice, DraxReadExt, DraxResult, DraxWriteExt, PacketComponent, Size, TransportError,
+};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+#[cfg(feature = "uuid")]
 use uuid::Uuid;
 
 mod var_num {
@@ -22,12 +26,17 @@ mod var_num {
             $size_fn:ident,
             $read_fn:ident,
             $read_struct:ident,
+            $read_opt_fn:ident,
+            $read_opt_struct:ident,
+            $read_with_first_fn:ident,
             $write_fn:ident,
             $write_struct:ident,
+            $bytes_fn:ident,
+            $max_bytes:literal,
             $bit_limit:literal,
             $and_check:literal
         ) => {
-            pub fn $size_fn(var_num: $typing) -> usize {
+            pub const fn $size_fn(var_num: $typing) -> usize {
                 let mut temp: $sub_typing = var_num as $sub_typing;
                 let mut size = 0;
                 loop {
@@ -39,6 +48,23 @@ mod var_num {
                 }
             }
 
+            /// Encodes the value into a stack buffer, returning the buffer and the number of
+            /// bytes used.
+            pub fn $bytes_fn(var_num: $typing) -> ([u8; $max_bytes], usize) {
+                let mut bytes = [0u8; $max_bytes];
+                let mut value: $sub_typing = var_num as $sub_typing;
+                let mut len = 0;
+                loop {
+                    if (value & $and_check) == 0 {
+                        bytes[len] = value as u8;
+                        return (bytes, len + 1);
+                    }
+                    bytes[len] = (value & 0x7F | 0x80) as u8;
+                    len += 1;
+                    value = value.overflowing_shr(7).0;
+                }
+            }
+
             pub(crate) fn $read_fn<A>(reader: &mut A) -> $read_struct<A>
             where
                 A: AsyncRead + Unpin + ?Sized,
@@ -96,13 +122,80 @@ mod var_num {
                 }
             }
 
+            /// Reads a variable number whose first byte was already consumed, such as a packet id
+            /// peeked for dispatch, continuing with the reader only if the first byte has its
+            /// continuation bit set.
+            pub async fn $read_with_first_fn<A>(first: u8, reader: &mut A) -> DraxResult<$typing>
+            where
+                A: AsyncRead + Unpin + ?Sized,
+            {
+                let value = <$typing>::from(first & 0b0111_1111);
+                if first & 0b1000_0000 == 0 {
+                    return Ok(value);
+                }
+                $read_struct {
+                    reader,
+                    value,
+                    bit_offset: 7,
+                    _pin: PhantomPinned,
+                }
+                .await
+            }
+
+            pub(crate) fn $read_opt_fn<A>(reader: &mut A) -> $read_opt_struct<A>
+            where
+                A: AsyncRead + Unpin + ?Sized,
+            {
+                $read_opt_struct {
+                    inner: $read_fn(reader),
+                }
+            }
+
+            pin_project! {
+                /// Reads a variable number, resolving to `None` if the stream ends before the
+                /// first byte. A stream ending part way through the number is still an `EOF`
+                /// error.
+                #[derive(Debug)]
+                #[must_use = "futures do nothing unless you `.await` or poll them"]
+                pub struct $read_opt_struct<'a, A: ?Sized> {
+                    #[pin]
+                    inner: $read_struct<'a, A>,
+                }
+            }
+
+            impl<A> Future for $read_opt_struct<'_, A>
+            where
+                A: AsyncRead + Unpin + ?Sized,
+            {
+                type Output = DraxResult<Option<$typing>>;
+
+                fn poll(
+                    self: Pin<&mut Self>,
+                    cx: &mut Context<'_>,
+                ) -> Poll<DraxResult<Option<$typing>>> {
+                    let mut me = self.project();
+
+                    match ready!(me.inner.as_mut().poll(cx)) {
+                        Ok(value) => Poll::Ready(Ok(Some(value))),
+                        // No bits were read, the stream ended cleanly before the number.
+                        Err(TransportError::EOF) if *me.inner.project().bit_offset == 0 => {
+                            Poll::Ready(Ok(None))
+                        }
+                        Err(error) => Poll::Ready(Err(error)),
+                    }
+                }
+            }
+
             pub(crate) fn $write_fn<A>(writer: &mut A, value: $typing) -> $write_struct<A>
             where
                 A: AsyncWrite + Unpin + ?Sized,
             {
+                let (bytes, len) = $bytes_fn(value);
                 $write_struct {
                     writer,
-                    value,
+                    bytes,
+                    len,
+                    written: 0,
                     _pin: PhantomPinned,
                 }
             }
@@ -112,7 +205,9 @@ mod var_num {
                 #[must_use = "futures do nothing unless you `.await` or poll them"]
                 pub struct $write_struct<'a, A: ?Sized> {
                     writer: &'a mut A,
-                    value: $typing,
+                    bytes: [u8; $max_bytes],
+                    len: usize,
+                    written: usize,
                     // Make this future `!Unpin` for compatibility with async trait methods.
                     #[pin]
                     _pin: PhantomPinned,
@@ -128,17 +223,18 @@ mod var_num {
                 fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<DraxResult<()>> {
                     let me = self.project();
 
-                    let mut value: $sub_typing = *me.value as $sub_typing;
-                    loop {
-                        if (value & $and_check) == 0 {
-                            ready!(Pin::new(&mut *me.writer).poll_write(cx, &[value as u8]))?;
-                            return Poll::Ready(Ok(()));
+                    while *me.written < *me.len {
+                        let n = ready!(Pin::new(&mut *me.writer)
+                            .poll_write(cx, &me.bytes[*me.written..*me.len]))?;
+                        if n == 0 {
+                            return Poll::Ready(Err(std::io::Error::from(
+                                std::io::ErrorKind::WriteZero,
+                            )
+                            .into()));
                         }
-                        ready!(Pin::new(&mut *me.writer)
-                            .poll_write(cx, &[(value & 0x7F | 0x80) as u8]))?;
-                        value = value.overflowing_shr(7).0;
-                        *me.value = value.try_into().unwrap();
+                        *me.written += n;
                     }
+                    Poll::Ready(Ok(()))
                 }
             }
         };
@@ -150,8 +246,13 @@ mod var_num {
         size_var_int,
         read_var_int,
         ReadVarInt,
+        read_var_int_opt,
+        ReadVarIntOpt,
+        read_var_int_with_first,
         write_var_int,
         WriteVarInt,
+        var_int_bytes,
+        5,
         35,
         0xFFFFFF80u32
     );
@@ -162,23 +263,70 @@ mod var_num {
         size_var_long,
         read_var_long,
         ReadVarLong,
+        read_var_long_opt,
+        ReadVarLongOpt,
+        read_var_long_with_first,
         write_var_long,
         WriteVarLong,
+        var_long_bytes,
+        10,
         70,
         0xFFFFFFFFFFFFFF80u64
     );
 }
-pub(crate) use var_num::{read_var_int, read_var_long, write_var_int, write_var_long};
+pub(crate) use var_num::{
+    read_var_int, read_var_int_opt, read_var_long, read_var_long_opt, write_var_int, write_var_long,
+};
 pub use var_num::{
-    size_var_int, size_var_long, ReadVarInt, ReadVarLong, WriteVarInt, WriteVarLong,
+    read_var_int_with_first, read_var_long_with_first, size_var_int, size_var_long, var_int_bytes,
+    var_long_bytes, ReadVarInt, ReadVarIntOpt, ReadVarLong, ReadVarLongOpt, WriteVarInt,
+    WriteVarLong,
 };
 
+/// Splits `len` bytes off the front of the slice, failing with `TransportError::EOF` if the
+/// slice is too short.
+pub(crate) fn split_slice(bytes: &[u8], len: usize) -> DraxResult<(&[u8], &[u8])> {
+    if bytes.len() < len {
+        return Err(TransportError::EOF);
+    }
+    Ok(bytes.split_at(len))
+}
+
+/// Decodes a variable length number of at most `max_bytes` bytes from the front of the slice.
+fn var_num_slice(bytes: &[u8], max_bytes: usize) -> DraxResult<(u64, &[u8])> {
+    let mut value = 0u64;
+    for index in 0..max_bytes {
+        let Some(byte) = bytes.get(index) else {
+            return Err(TransportError::EOF);
+        };
+        value |= u64::from(byte & 0b0111_1111) << (7 * index);
+        if byte & 0b1000_0000 == 0 {
+            return Ok((value, &bytes[index + 1..]));
+        }
+    }
+    Err(TransportError::VarNumTooLarge)
+}
+
+/// Decodes a VarInt length from the front of the slice, rejecting negative lengths.
+pub(crate) fn len_slice<'a>(
+    bytes: &'a [u8],
+    action: &'static str,
+) -> DraxResult<(usize, &'a [u8])> {
+    let (len, rest) = VarInt::decode_slice(bytes)?;
+    if len < 0 {
+        return TransportError::limit_exceeded(0, len, action);
+    }
+    Ok((len as usize, rest))
+}
+
 macro_rules! define_primitive_bind {
     ($($prim:ty),*) => {
         $(
             impl<C: Send + Sync> PacketComponent<C> for $prim {
                 type ComponentType = $prim;
 
+                const FIXED_SIZE: Option<usize> = Some(size_of::<$prim>());
+
                 decode!(read {
                     let mut buf = [0; size_of::<Self>()];
                     read.read_exact(&mut buf).await?;
@@ -193,15 +341,196 @@ macro_rules! define_primitive_bind {
                     Ok(Size::Constant(size_of::<Self>()))
                 }
             }
+
+            impl DecodeSlice for $prim {
+                fn decode_slice(bytes: &[u8]) -> DraxResult<(Self, &[u8])> {
+                    let (value, rest) = split_slice(bytes, size_of::<Self>())?;
+                    Ok((Self::from_be_bytes(value.try_into().unwrap()), rest))
+                }
+            }
+
+            impl<C: Send + Sync> PacketComponent<C> for LittleEndian<$prim> {
+                type ComponentType = $prim;
+
+                const FIXED_SIZE: Option<usize> = Some(size_of::<$prim>());
+
+                decode!(read {
+                    let mut buf = [0; size_of::<$prim>()];
+                    read.read_exact(&mut buf).await?;
+                    Ok(<$prim>::from_le_bytes(buf))
+                });
+
+                encode!(component_ref, write {
+                    write.write_all(component_ref.to_le_bytes().as_ref()).await?;
+                });
+
+                fn size(_: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+                    Ok(Size::Constant(size_of::<$prim>()))
+                }
+            }
         )*
     }
 }
 
+/// A delegate struct which encodes and decodes a numeric primitive in little-endian byte order.
+///
+/// The primitive implementations are all big-endian, this delegate can be used for protocols
+/// which expect the least significant byte first.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<LittleEndian<u16>>(&1).await?;
+/// assert_eq!(cursor.clone().into_inner(), vec![1, 0]);
+/// cursor.set_position(0);
+/// let back = cursor.decode_component::<LittleEndian<u16>>().await?;
+/// assert_eq!(back, 1);
+/// # Ok(())
+/// # }
+/// ```
+pub struct LittleEndian<T>(PhantomData<T>);
+
 define_primitive_bind!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
 
+shared_without_context!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+shared_without_context!(
+    LittleEndian<u8>,
+    LittleEndian<u16>,
+    LittleEndian<u32>,
+    LittleEndian<u64>,
+    LittleEndian<i8>,
+    LittleEndian<i16>,
+    LittleEndian<i32>,
+    LittleEndian<i64>,
+    LittleEndian<f32>,
+    LittleEndian<f64>
+);
+/// A delegate struct which encodes and decodes a `u32` as an unsigned 24-bit integer occupying
+/// exactly 3 bytes, big-endian unless wrapped in [`LittleEndian`].
+///
+/// Values above `0xFFFFFF` are rejected with `TransportError::LimitExceeded` when encoding.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<U24>(&0x010203).await?;
+/// cursor.encode_component::<LittleEndian<U24>>(&0x010203).await?;
+/// assert_eq!(cursor.get_ref(), &vec![1, 2, 3, 3, 2, 1]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct U24;
+
+impl U24 {
+    const MAX: u32 = 0xFF_FFFF;
+
+    fn to_be_bytes(value: u32) -> DraxResult<[u8; 3]> {
+        if value > Self::MAX {
+            return TransportError::limit_exceeded(
+                Self::MAX as i32,
+                value.min(i32::MAX as u32) as i32,
+                "encoding u24",
+            );
+        }
+        let [_, high, mid, low] = value.to_be_bytes();
+        Ok([high, mid, low])
+    }
+
+    fn from_be_bytes([high, mid, low]: [u8; 3]) -> u32 {
+        u32::from_be_bytes([0, high, mid, low])
+    }
+}
+
+/// A delegate struct which encodes and decodes an `i32` as a signed 24-bit two's complement
+/// integer occupying exactly 3 bytes, big-endian unless wrapped in [`LittleEndian`].
+///
+/// Decoded values are sign extended. Values outside of `-0x800000..=0x7FFFFF` are rejected with
+/// `TransportError::LimitExceeded` when encoding.
+pub struct I24;
+
+impl I24 {
+    const MIN: i32 = -0x80_0000;
+    const MAX: i32 = 0x7F_FFFF;
+
+    fn to_be_bytes(value: i32) -> DraxResult<[u8; 3]> {
+        if value > Self::MAX {
+            return TransportError::limit_exceeded(Self::MAX, value, "encoding i24");
+        }
+        if value < Self::MIN {
+            return TransportError::limit_exceeded(Self::MIN, value, "encoding i24");
+        }
+        let [_, high, mid, low] = value.to_be_bytes();
+        Ok([high, mid, low])
+    }
+
+    fn from_be_bytes([high, mid, low]: [u8; 3]) -> i32 {
+        i32::from_be_bytes([high, mid, low, 0]) >> 8
+    }
+}
+
+macro_rules! define_24_bit_bind {
+    ($($delegate:ty => $prim:ty),*) => {
+        $(
+            impl<C: Send + Sync> PacketComponent<C> for $delegate {
+                type ComponentType = $prim;
+
+                const FIXED_SIZE: Option<usize> = Some(3);
+
+                decode!(read {
+                    let mut buf = [0; 3];
+                    read.read_exact(&mut buf).await?;
+                    Ok(<$delegate>::from_be_bytes(buf))
+                });
+
+                encode!(component_ref, write {
+                    write.write_all(&<$delegate>::to_be_bytes(*component_ref)?).await?;
+                });
+
+                fn size(_: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+                    Ok(Size::Constant(3))
+                }
+            }
+
+            impl<C: Send + Sync> PacketComponent<C> for LittleEndian<$delegate> {
+                type ComponentType = $prim;
+
+                const FIXED_SIZE: Option<usize> = Some(3);
+
+                decode!(read {
+                    let mut buf = [0; 3];
+                    read.read_exact(&mut buf).await?;
+                    buf.reverse();
+                    Ok(<$delegate>::from_be_bytes(buf))
+                });
+
+                encode!(component_ref, write {
+                    let mut buf = <$delegate>::to_be_bytes(*component_ref)?;
+                    buf.reverse();
+                    write.write_all(&buf).await?;
+                });
+
+                fn size(_: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+                    Ok(Size::Constant(3))
+                }
+            }
+        )*
+    }
+}
+
+define_24_bit_bind!(U24 => u32, I24 => i32);
+
+shared_without_context!(U24, I24, LittleEndian<U24>, LittleEndian<I24>);
+shared_without_context!((), bool, VarInt, VarLong, ZeroByte);
+
 impl<C: Send + Sync> PacketComponent<C> for () {
     type ComponentType = ();
 
+    const FIXED_SIZE: Option<usize> = Some(0);
+
     decode!(_read Ok(()));
 
     encode!(_component_ref, _write);
@@ -211,9 +540,99 @@ impl<C: Send + Sync> PacketComponent<C> for () {
     }
 }
 
+/// Tuples encode their elements one after another in order, each through its own component.
+///
+/// This lets a heterogeneous fixed group such as `(VarInt, bool)` be used wherever a single
+/// component is expected, including as a field of a larger component. Tuples nest, and the size
+/// is only fixed if the size of every element is.
+macro_rules! define_tuple_bind {
+    ($(($($element:ident $index:tt),+)),*) => {$(
+        impl<C: Send + Sync, $($element),+> PacketComponent<C> for ($($element,)+)
+        where
+            $($element: PacketComponent<C>),+
+        {
+            type ComponentType = ($($element::ComponentType,)+);
+
+            const FIXED_SIZE: Option<usize> = {
+                let total = Some(0usize);
+                $(
+                    let total = match (total, $element::FIXED_SIZE) {
+                        (Some(total), Some(size)) => total.checked_add(size),
+                        _ => None,
+                    };
+                )+
+                total
+            };
+
+            decode!(read, context {
+                Ok(($($element::decode(context, read).await?,)+))
+            });
+
+            encode!(component_ref, write, context {
+                $($element::encode(&component_ref.$index, context, write).await?;)+
+            });
+
+            fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+                let size = Size::Constant(0);
+                $(let size = size.checked_add($element::size(&input.$index, context)?)?;)+
+                Ok(size)
+            }
+        }
+    )*};
+}
+
+define_tuple_bind!(
+    (T0 0),
+    (T0 0, T1 1),
+    (T0 0, T1 1, T2 2),
+    (T0 0, T1 1, T2 2, T3 3),
+    (T0 0, T1 1, T2 2, T3 3, T4 4),
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5),
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6),
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7)
+);
+
+/// A delegate struct which encodes and decodes a reserved byte which must always be zero.
+///
+/// Unlike `()` the field occupies a byte on the wire, decoding any other value fails with
+/// `TransportError::ReservedByte`.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<ZeroByte>(&()).await?;
+/// assert_eq!(cursor.get_ref(), &vec![0]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct ZeroByte;
+
+impl<C: Send + Sync> PacketComponent<C> for ZeroByte {
+    type ComponentType = ();
+
+    const FIXED_SIZE: Option<usize> = Some(1);
+
+    decode!(read {
+        match read.read_u8().await? {
+            0 => Ok(()),
+            byte => TransportError::reserved_byte(byte),
+        }
+    });
+
+    encode!(_component_ref, write write.write_u8(0).await?);
+
+    fn size(_: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+        Ok(Size::Constant(1))
+    }
+}
+
 impl<C: Send + Sync> PacketComponent<C> for bool {
     type ComponentType = bool;
 
+    const FIXED_SIZE: Option<usize> = Some(1);
+
     decode!(read Ok(read.read_u8().await? != 0x0));
 
     encode!(component_ref, write write.write_u8(if *component_ref { 0x1 } else { 0x0 }).await?);
@@ -223,6 +642,13 @@ impl<C: Send + Sync> PacketComponent<C> for bool {
     }
 }
 
+impl DecodeSlice for bool {
+    fn decode_slice(bytes: &[u8]) -> DraxResult<(Self, &[u8])> {
+        let (value, rest) = split_slice(bytes, 1)?;
+        Ok((value[0] != 0x0, rest))
+    }
+}
+
 /// A delegate struct which encodes and decodes an `i32` type.
 ///
 /// This delegate will attempt to encode the integer using the smallest possible
@@ -230,6 +656,14 @@ impl<C: Send + Sync> PacketComponent<C> for bool {
 /// fully read.
 pub struct VarInt;
 
+impl VarInt {
+    /// Returns the number of bytes the value occupies on the wire, the same as
+    /// [`size_var_int`].
+    pub const fn wire_size(value: i32) -> usize {
+        size_var_int(value)
+    }
+}
+
 impl<C: Send + Sync> PacketComponent<C> for VarInt {
     type ComponentType = i32;
 
@@ -242,6 +676,13 @@ impl<C: Send + Sync> PacketComponent<C> for VarInt {
     }
 }
 
+impl DecodeSlice for VarInt {
+    fn decode_slice(bytes: &[u8]) -> DraxResult<(i32, &[u8])> {
+        let (value, rest) = var_num_slice(bytes, 5)?;
+        Ok((value as u32 as i32, rest))
+    }
+}
+
 /// A delegate struct which encodes and decodes a `i64` type.
 ///
 /// This delegate will attempt to encode the long using the smallest possible
@@ -249,6 +690,14 @@ impl<C: Send + Sync> PacketComponent<C> for VarInt {
 /// fully read.
 pub struct VarLong;
 
+impl VarLong {
+    /// Returns the number of bytes the value occupies on the wire, the same as
+    /// [`size_var_long`].
+    pub const fn wire_size(value: i64) -> usize {
+        size_var_long(value)
+    }
+}
+
 impl<C: Send + Sync> PacketComponent<C> for VarLong {
     type ComponentType = i64;
 
@@ -261,10 +710,237 @@ impl<C: Send + Sync> PacketComponent<C> for VarLong {
     }
 }
 
+impl DecodeSlice for VarLong {
+    fn decode_slice(bytes: &[u8]) -> DraxResult<(i64, &[u8])> {
+        let (value, rest) = var_num_slice(bytes, 10)?;
+        Ok((value as i64, rest))
+    }
+}
+
+/// A delegate struct which encodes and decodes an `i32` index into a table of `MAX` entries as
+/// a VarInt.
+///
+/// Indices outside of `0..MAX` are rejected with `TransportError::IndexOutOfBounds`, both when
+/// decoding and encoding.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![3]);
+/// assert_eq!(cursor.decode_component::<Index<4>>().await?, 3);
+///
+/// let mut cursor = Cursor::new(vec![4]);
+/// let error = cursor.decode_component::<Index<4>>().await;
+/// assert!(matches!(error, Err(TransportError::IndexOutOfBounds { index: 4, len: 4 })));
+/// # Ok(())
+/// # }
+/// ```
+pub struct Index<const MAX: i32>;
+
+impl<const MAX: i32> Index<MAX> {
+    fn check(index: i32) -> DraxResult<i32> {
+        if (0..MAX).contains(&index) {
+            Ok(index)
+        } else {
+            TransportError::index_out_of_bounds(index, MAX)
+        }
+    }
+}
+
+impl<C: Send + Sync, const MAX: i32> PacketComponent<C> for Index<MAX> {
+    type ComponentType = i32;
+
+    decode!(read Self::check(read.read_var_int().await?));
+
+    encode!(component_ref, write write.write_var_int(Self::check(*component_ref)?).await?);
+
+    fn size(input: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+        Ok(Size::Dynamic(size_var_int(*input)))
+    }
+}
+
+/// A delegate struct which encodes and decodes an integer through the component `T`, requiring
+/// the value to lie within `MIN..=MAX`.
+///
+/// Values outside the range are rejected with `TransportError::LimitExceeded`, both when
+/// decoding and encoding, reporting the violated bound and the value clamped to an `i32`.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![8, 9]);
+/// assert_eq!(cursor.decode_component::<Ranged<u8, 0, 8>>().await?, 8);
+/// let error = cursor.decode_component::<Ranged<u8, 0, 8>>().await;
+/// assert!(matches!(error, Err(TransportError::LimitExceeded(8, 9, _))));
+/// # Ok(())
+/// # }
+/// ```
+pub struct Ranged<T, const MIN: i64, const MAX: i64>(PhantomData<T>);
+
+impl<T, const MIN: i64, const MAX: i64> Ranged<T, MIN, MAX> {
+    fn clamp(value: i64) -> i32 {
+        value.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+    }
+
+    fn check<V: Copy + TryInto<i64>>(value: V, action: &'static str) -> DraxResult<V> {
+        match value.try_into() {
+            Ok(wide) if wide < MIN => {
+                TransportError::limit_exceeded(Self::clamp(MIN), Self::clamp(wide), action)
+            }
+            Ok(wide) if wide <= MAX => Ok(value),
+            Ok(wide) => TransportError::limit_exceeded(Self::clamp(MAX), Self::clamp(wide), action),
+            Err(_) => TransportError::limit_exceeded(Self::clamp(MAX), i32::MAX, action),
+        }
+    }
+}
+
+impl<C: Send + Sync, T, const MIN: i64, const MAX: i64> PacketComponent<C> for Ranged<T, MIN, MAX>
+where
+    T: PacketComponent<C>,
+    T::ComponentType: Copy + TryInto<i64>,
+{
+    type ComponentType = T::ComponentType;
+
+    const FIXED_SIZE: Option<usize> = T::FIXED_SIZE;
+
+    decode!(read, context {
+        Self::check(T::decode(context, read).await?, "decoding ranged value")
+    });
+
+    encode!(component_ref, write, context {
+        let value = Self::check(*component_ref, "encoding ranged value")?;
+        T::encode(&value, context, write).await?
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        T::size(input, context)
+    }
+}
+
+const BINCODE_U16_TAG: u8 = 251;
+const BINCODE_U32_TAG: u8 = 252;
+const BINCODE_U64_TAG: u8 = 253;
+const BINCODE_U128_TAG: u8 = 254;
+
+fn bincode_var_u64_bytes(value: u64) -> ([u8; 9], usize) {
+    let mut bytes = [0; 9];
+    if value < BINCODE_U16_TAG as u64 {
+        bytes[0] = value as u8;
+        return (bytes, 1);
+    }
+    if value <= u16::MAX as u64 {
+        bytes[0] = BINCODE_U16_TAG;
+        bytes[1..3].copy_from_slice(&(value as u16).to_le_bytes());
+        (bytes, 3)
+    } else if value <= u32::MAX as u64 {
+        bytes[0] = BINCODE_U32_TAG;
+        bytes[1..5].copy_from_slice(&(value as u32).to_le_bytes());
+        (bytes, 5)
+    } else {
+        bytes[0] = BINCODE_U64_TAG;
+        bytes[1..9].copy_from_slice(&value.to_le_bytes());
+        (bytes, 9)
+    }
+}
+
+async fn read_bincode_var_u64<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+    read: &mut A,
+) -> DraxResult<u64> {
+    match read.read_u8().await? {
+        BINCODE_U16_TAG => Ok(read.read_u16_le().await? as u64),
+        BINCODE_U32_TAG => Ok(read.read_u32_le().await? as u64),
+        BINCODE_U64_TAG => Ok(read.read_u64_le().await?),
+        BINCODE_U128_TAG => Err(TransportError::VarNumTooLarge),
+        tag @ 255 => TransportError::invalid_variant(tag as i32),
+        value => Ok(value as u64),
+    }
+}
+
+const fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+const fn zigzag_decode(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+/// A delegate struct which encodes and decodes a `u64` using bincode's variable-length integer
+/// encoding, as used by `bincode::DefaultOptions`.
+///
+/// Unlike `VarLong` this is not a LEB128 style encoding, the first byte either holds the value
+/// or selects the width of the little-endian integer which follows it:
+///
+/// | Value                  | Bytes                      |
+/// |------------------------|----------------------------|
+/// | `0..=250`              | `value as u8`              |
+/// | `251..=u16::MAX`       | `251` then `u16` LE        |
+/// | `u16::MAX+1..=u32::MAX`| `252` then `u32` LE        |
+/// | above `u32::MAX`       | `253` then `u64` LE        |
+///
+/// The tag `254` introduces a `u128`, decoding it fails with `TransportError::VarNumTooLarge`.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<BincodeVarU64>(&300).await?;
+/// assert_eq!(cursor.get_ref(), &vec![251, 44, 1]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct BincodeVarU64;
+
+impl<C: Send + Sync> PacketComponent<C> for BincodeVarU64 {
+    type ComponentType = u64;
+
+    decode!(read read_bincode_var_u64(read).await);
+
+    encode!(component_ref, write {
+        let (bytes, len) = bincode_var_u64_bytes(*component_ref);
+        write.write_all(&bytes[..len]).await?;
+    });
+
+    fn size(input: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+        Ok(Size::Dynamic(bincode_var_u64_bytes(*input).1))
+    }
+}
+
+/// A delegate struct which encodes and decodes an `i64` using bincode's variable-length integer
+/// encoding for signed integers.
+///
+/// The value is zigzag encoded, mapping `0, -1, 1, -2, ...` to `0, 1, 2, 3, ...`, and the result
+/// is written as a [`BincodeVarU64`]. Smaller signed integers encoded by bincode use the same
+/// format, so any of them can be decoded with this delegate.
+pub struct BincodeVarInt;
+
+impl<C: Send + Sync> PacketComponent<C> for BincodeVarInt {
+    type ComponentType = i64;
+
+    decode!(read Ok(zigzag_decode(read_bincode_var_u64(read).await?)));
+
+    encode!(component_ref, write {
+        let (bytes, len) = bincode_var_u64_bytes(zigzag_encode(*component_ref));
+        write.write_all(&bytes[..len]).await?;
+    });
+
+    fn size(input: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+        Ok(Size::Dynamic(
+            bincode_var_u64_bytes(zigzag_encode(*input)).1,
+        ))
+    }
+}
+
+shared_without_context!(BincodeVarU64, BincodeVarInt);
+
 #[cfg(feature = "uuid")]
 impl<C: Send + Sync> PacketComponent<C> for Uuid {
     type ComponentType = Uuid;
 
+    const FIXED_SIZE: Option<usize> = Some(size_of::<u64>() * 2);
+
     decode!(read {
         let mut buf = [0; 16];
         read.read_exact(&mut buf).await?;
@@ -281,9 +957,21 @@ impl<C: Send + Sync> PacketComponent<C> for Uuid {
     }
 }
 
+#[cfg(feature = "uuid")]
+shared_without_context!(Uuid);
+
 #[cfg(test)]
 mod test {
-    use crate::prelude::{DraxReadExt, DraxResult, DraxWriteExt};
+    use crate::delegate::primitive::{
+        read_var_int_with_first, read_var_long_with_first, size_var_int, size_var_long,
+        var_int_bytes, var_long_bytes,
+    };
+    use crate::prelude::{
+        BincodeVarInt, BincodeVarU64, DecodeSlice, DraxReadExt, DraxResult, DraxWriteExt, Index,
+        LittleEndian, PacketComponent, Ranged, Size, TransportError, VarInt, VarLong, VecU8,
+        ZeroByte, I24, U24,
+    };
+    use std::assert_matches::assert_matches;
     use std::io::Cursor;
     use uuid::Uuid;
 
@@ -333,6 +1021,265 @@ mod test {
         Ok(())
     }
 
+    const PREFIX_LEN: usize = size_var_int(300);
+    const _: () = assert!(PREFIX_LEN == 2);
+    const _: () = assert!(size_var_long(i64::MIN) == 10);
+
+    #[test]
+    fn test_const_var_num_size() {
+        let prefix = [0u8; PREFIX_LEN];
+        assert_eq!(prefix.len(), size_var_int(300));
+    }
+
+    #[tokio::test]
+    async fn test_index_in_bounds() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<Index<300>>(&299).await?;
+        assert_eq!(cursor.get_ref(), &vec![171, 2]);
+        assert_eq!(Index::<300>::size(&299, &mut ())?, Size::Dynamic(2));
+
+        cursor.set_position(0);
+        assert_eq!(cursor.decode_component::<Index<300>>().await?, 299);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_index_negative() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(-1).await?;
+        cursor.set_position(0);
+        assert_matches!(
+            cursor.decode_component::<Index<300>>().await,
+            Err(TransportError::IndexOutOfBounds {
+                index: -1,
+                len: 300
+            })
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_index_equal_to_max() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(300).await?;
+        cursor.set_position(0);
+        assert_matches!(
+            cursor.decode_component::<Index<300>>().await,
+            Err(TransportError::IndexOutOfBounds {
+                index: 300,
+                len: 300
+            })
+        );
+
+        let error = Cursor::new(vec![])
+            .encode_component::<Index<300>>(&300)
+            .await;
+        assert_matches!(error, Err(TransportError::IndexOutOfBounds { .. }));
+        Ok(())
+    }
+
+    #[derive(Debug, Eq, PartialEq)]
+    struct Flagged {
+        id: i32,
+        entry: (i32, bool),
+        nested: ((u8, i32), bool),
+    }
+
+    impl<C: Send + Sync> PacketComponent<C> for Flagged {
+        type ComponentType = Self;
+
+        decode!(read, context {
+            Ok(Flagged {
+                id: VarInt::decode(context, read).await?,
+                entry: <(VarInt, bool)>::decode(context, read).await?,
+                nested: <((u8, VarInt), bool)>::decode(context, read).await?,
+            })
+        });
+
+        encode!(component_ref, write, context {
+            VarInt::encode(&component_ref.id, context, write).await?;
+            <(VarInt, bool)>::encode(&component_ref.entry, context, write).await?;
+            <((u8, VarInt), bool)>::encode(&component_ref.nested, context, write).await?;
+        });
+
+        fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+            VarInt::size(&input.id, context)?
+                .checked_add(<(VarInt, bool)>::size(&input.entry, context)?)?
+                .checked_add(<((u8, VarInt), bool)>::size(&input.nested, context)?)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tuple_fields() -> DraxResult<()> {
+        let value = Flagged {
+            id: 1,
+            entry: (300, true),
+            nested: ((7, -1), false),
+        };
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<Flagged>(&value).await?;
+        assert_eq!(
+            cursor.get_ref(),
+            &vec![1, 172, 2, 1, 7, 255, 255, 255, 255, 15, 0]
+        );
+        assert_eq!(Flagged::size(&value, &mut ())?, Size::Dynamic(11));
+
+        cursor.set_position(0);
+        assert_eq!(cursor.decode_component::<Flagged>().await?, value);
+
+        assert_eq!(
+            <(u8, (i32, bool)) as PacketComponent<()>>::FIXED_SIZE,
+            Some(6)
+        );
+        assert_eq!(<(u8, VarInt) as PacketComponent<()>>::FIXED_SIZE, None);
+        assert_eq!(<(u8, bool)>::size(&(1, true), &mut ())?, Size::Constant(2));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_zero_byte() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<ZeroByte>(&()).await?;
+        cursor.encode_component::<()>(&()).await?;
+        assert_eq!(cursor.get_ref(), &vec![0]);
+        assert_eq!(ZeroByte::size(&(), &mut ())?, Size::Constant(1));
+
+        cursor.set_position(0);
+        cursor.decode_component::<ZeroByte>().await?;
+        assert_eq!(cursor.position(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_zero_byte_rejects_non_zero() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![0x7F]);
+        assert_matches!(
+            cursor.decode_component::<ZeroByte>().await,
+            Err(TransportError::ReservedByte(0x7F))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_u24() -> DraxResult<()> {
+        for (value, bytes) in [(0, [0, 0, 0]), (0x010203, [1, 2, 3]), (0xFFFFFF, [255; 3])] {
+            let mut cursor = Cursor::new(vec![]);
+            cursor.encode_component::<U24>(&value).await?;
+            assert_eq!(cursor.get_ref(), &bytes);
+            cursor.set_position(0);
+            assert_eq!(cursor.decode_component::<U24>().await?, value);
+        }
+        assert_eq!(U24::size(&0, &mut ())?, Size::Constant(3));
+
+        let error = Cursor::new(vec![])
+            .encode_component::<U24>(&0x1000000)
+            .await;
+        assert_matches!(
+            error,
+            Err(TransportError::LimitExceeded(
+                0xFFFFFF,
+                0x1000000,
+                "encoding u24"
+            ))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_i24_sign_extension() -> DraxResult<()> {
+        for (value, bytes) in [
+            (0, [0, 0, 0]),
+            (-1, [255, 255, 255]),
+            (0x7FFFFF, [127, 255, 255]),
+            (-0x800000, [128, 0, 0]),
+            (-2, [255, 255, 254]),
+        ] {
+            let mut cursor = Cursor::new(vec![]);
+            cursor.encode_component::<I24>(&value).await?;
+            assert_eq!(cursor.get_ref(), &bytes);
+            cursor.set_position(0);
+            assert_eq!(cursor.decode_component::<I24>().await?, value);
+
+            let mut cursor = Cursor::new(vec![]);
+            cursor.encode_component::<LittleEndian<I24>>(&value).await?;
+            let mut reversed = bytes;
+            reversed.reverse();
+            assert_eq!(cursor.get_ref(), &reversed);
+            cursor.set_position(0);
+            assert_eq!(cursor.decode_component::<LittleEndian<I24>>().await?, value);
+        }
+
+        let error = Cursor::new(vec![]).encode_component::<I24>(&0x800000).await;
+        assert_matches!(
+            error,
+            Err(TransportError::LimitExceeded(0x7FFFFF, 0x800000, _))
+        );
+        let error = Cursor::new(vec![])
+            .encode_component::<I24>(&-0x800001)
+            .await;
+        assert_matches!(
+            error,
+            Err(TransportError::LimitExceeded(-0x800000, -0x800001, _))
+        );
+        Ok(())
+    }
+
+    const _: () = assert!(VarInt::wire_size(300) == 2);
+
+    #[test]
+    fn test_wire_size() {
+        assert_eq!(VarInt::wire_size(300), 2);
+        assert_eq!(VarInt::wire_size(-1), 5);
+        assert_eq!(VarLong::wire_size(300), 2);
+        assert_eq!(VarLong::wire_size(i64::MIN), 10);
+        for attempt in VAR_INT_TESTS {
+            assert_eq!(VarInt::wire_size(attempt.0), attempt.1.len());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_var_int_opt() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        assert_eq!(cursor.read_var_int_opt().await?, None);
+
+        let mut cursor = Cursor::new(vec![172, 2]);
+        assert_eq!(cursor.read_var_int_opt().await?, Some(300));
+        assert_eq!(cursor.read_var_int_opt().await?, None);
+
+        let mut cursor = Cursor::new(vec![0x80]);
+        assert_matches!(cursor.read_var_int_opt().await, Err(TransportError::EOF));
+
+        let mut cursor = Cursor::new(vec![0x80]);
+        assert_matches!(cursor.read_var_long_opt().await, Err(TransportError::EOF));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_var_int_bytes() -> DraxResult<()> {
+        for attempt in VAR_INT_TESTS {
+            let (bytes, len) = var_int_bytes(attempt.0);
+            assert_eq!(&bytes[..len], attempt.1);
+
+            let mut cursor = Cursor::new(vec![]);
+            cursor.write_var_int(attempt.0).await?;
+            assert_eq!(&bytes[..len], cursor.get_ref().as_slice());
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_var_long_bytes() -> DraxResult<()> {
+        for value in [0, 25, -1, 55324, i64::MAX, i64::MIN, -8877777] {
+            let (bytes, len) = var_long_bytes(value);
+            assert_eq!(len, size_var_long(value));
+
+            let mut cursor = Cursor::new(vec![]);
+            cursor.write_var_long(value).await?;
+            assert_eq!(&bytes[..len], cursor.get_ref().as_slice());
+        }
+        Ok(())
+    }
+
     primitive_tests!(u8; 10; test_u8);
     primitive_tests!(+ ..
         u8, u16, test_u16;
@@ -348,6 +1295,32 @@ mod test {
     primitive_tests!(f32; 30.40; test_f32);
     primitive_tests!(f64; { f32::MAX as f64 + 30.40 }; test_f64);
 
+    macro_rules! little_endian_tests {
+        ($($testable_ty:ty; $testable_value:expr; $test_ident:ident),*) => {$(
+            #[tokio::test]
+            async fn $test_ident() -> DraxResult<()> {
+                let expected: $testable_ty = $testable_value;
+                let mut cursor = Cursor::new(vec![]);
+                cursor.encode_component::<LittleEndian<$testable_ty>>(&expected).await?;
+                assert_eq!(cursor.get_ref().as_slice(), expected.to_le_bytes());
+
+                let mut big_endian = Cursor::new(vec![]);
+                big_endian.encode_own_component(&expected).await?;
+                assert_ne!(cursor.get_ref(), big_endian.get_ref());
+
+                cursor.set_position(0);
+                let back = cursor.decode_component::<LittleEndian<$testable_ty>>().await?;
+                assert_eq!(back, expected);
+                Ok(())
+            }
+        )*};
+    }
+
+    little_endian_tests!(
+        u32; 0x0102_0304; test_little_endian_u32,
+        i64; -0x0102_0304_0506_0708; test_little_endian_i64
+    );
+
     #[cfg(feature = "uuid")]
     #[tokio::test]
     async fn test_uuid() -> DraxResult<()> {
@@ -359,4 +1332,162 @@ mod test {
         assert_eq!(back, expected);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_bincode_var_u64_matches_bincode() -> DraxResult<()> {
+        use bincode::Options;
+
+        for value in [
+            0,
+            1,
+            250,
+            251,
+            300,
+            65535,
+            65536,
+            4_294_967_295,
+            4_294_967_296,
+            u64::MAX,
+        ] {
+            let expected = bincode::DefaultOptions::new().serialize(&value).unwrap();
+            let mut cursor = Cursor::new(vec![]);
+            cursor.encode_component::<BincodeVarU64>(&value).await?;
+            assert_eq!(cursor.get_ref(), &expected, "encoding {value}");
+            assert_eq!(
+                BincodeVarU64::size(&value, &mut ())?,
+                Size::Dynamic(expected.len())
+            );
+
+            cursor.set_position(0);
+            assert_eq!(cursor.decode_component::<BincodeVarU64>().await?, value);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bincode_var_int_matches_bincode() -> DraxResult<()> {
+        use bincode::Options;
+
+        for value in [0, -1, 1, -126, 125, -300, 40_000, i64::MIN, i64::MAX] {
+            let expected = bincode::DefaultOptions::new().serialize(&value).unwrap();
+            let mut cursor = Cursor::new(vec![]);
+            cursor.encode_component::<BincodeVarInt>(&value).await?;
+            assert_eq!(cursor.get_ref(), &expected, "encoding {value}");
+
+            cursor.set_position(0);
+            assert_eq!(cursor.decode_component::<BincodeVarInt>().await?, value);
+        }
+
+        let narrow = bincode::DefaultOptions::new()
+            .serialize(&-70_000i32)
+            .unwrap();
+        let mut cursor = Cursor::new(narrow);
+        assert_eq!(cursor.decode_component::<BincodeVarInt>().await?, -70_000);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bincode_var_u64_u128_tag() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![254]);
+        assert_matches!(
+            cursor.decode_component::<BincodeVarU64>().await,
+            Err(TransportError::VarNumTooLarge)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_decode_slice_sequence() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(-1).await?;
+        cursor.encode_component::<i16>(&-300).await?;
+        cursor.write_var_long(i64::MIN).await?;
+        cursor.encode_component::<bool>(&true).await?;
+        cursor.encode_component::<f64>(&1.5).await?;
+        cursor
+            .encode_component::<String>(&"slice".to_string())
+            .await?;
+        cursor.encode_component::<VecU8>(&vec![1, 2, 3]).await?;
+        cursor.write_var_int(300).await?;
+        let bytes = cursor.into_inner();
+
+        let (var_int, rest) = VarInt::decode_slice(&bytes)?;
+        let (short, rest) = i16::decode_slice(rest)?;
+        let (var_long, rest) = VarLong::decode_slice(rest)?;
+        let (flag, rest) = bool::decode_slice(rest)?;
+        let (double, rest) = f64::decode_slice(rest)?;
+        let (string, rest) = String::decode_slice(rest)?;
+        let (vec, rest) = VecU8::decode_slice(rest)?;
+        assert_eq!((var_int, short, var_long), (-1, -300, i64::MIN));
+        assert_eq!((flag, double), (true, 1.5));
+        assert_eq!(
+            (string.as_str(), vec.as_slice()),
+            ("slice", [1, 2, 3].as_slice())
+        );
+        assert_eq!(rest, [172, 2]);
+
+        assert_matches!(VarInt::decode_slice(&rest[..1]), Err(TransportError::EOF));
+        assert_matches!(i32::decode_slice(rest), Err(TransportError::EOF));
+        assert_matches!(
+            VarInt::decode_slice(&[0xFF; 6]),
+            Err(TransportError::VarNumTooLarge)
+        );
+        assert_matches!(String::decode_slice(&[2, b'a']), Err(TransportError::EOF));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_read_var_int_with_first() -> DraxResult<()> {
+        let mut rest: &[u8] = &[10];
+        assert_eq!(read_var_int_with_first(0x7F, &mut rest).await?, 127);
+        assert_eq!(rest, [10]);
+
+        let (bytes, len) = var_int_bytes(55324);
+        let mut rest = &bytes[1..len];
+        assert_eq!(read_var_int_with_first(bytes[0], &mut rest).await?, 55324);
+        assert!(rest.is_empty());
+
+        let (bytes, len) = var_int_bytes(-1);
+        let mut rest = &bytes[1..len];
+        assert_eq!(read_var_int_with_first(bytes[0], &mut rest).await?, -1);
+
+        let mut rest: &[u8] = &[0xFF; 4];
+        let error = read_var_int_with_first(0xFF, &mut rest).await;
+        assert_matches!(error, Err(TransportError::VarNumTooLarge));
+
+        let mut rest: &[u8] = &[];
+        let error = read_var_int_with_first(0x80, &mut rest).await;
+        assert_matches!(error, Err(TransportError::EOF));
+
+        let (bytes, len) = var_long_bytes(i64::MIN);
+        let mut rest = &bytes[1..len];
+        assert_eq!(
+            read_var_long_with_first(bytes[0], &mut rest).await?,
+            i64::MIN
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_ranged_bounds() -> DraxResult<()> {
+        type Slot = Ranged<u8, 1, 15>;
+
+        let mut bytes: &[u8] = &[1, 15, 0, 16];
+        assert_eq!(bytes.decode_component::<Slot>().await?, 1);
+        assert_eq!(bytes.decode_component::<Slot>().await?, 15);
+        let error = bytes.decode_component::<Slot>().await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(1, 0, _)));
+        let error = bytes.decode_component::<Slot>().await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(15, 16, _)));
+
+        let error = vec![].encode_component::<Slot>(&16).await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(15, 16, _)));
+        assert_eq!(<Slot as PacketComponent<()>>::FIXED_SIZE, Some(1));
+
+        let error = vec![]
+            .encode_component::<Ranged<u64, 0, 10>>(&u64::MAX)
+            .await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(10, i32::MAX, _)));
+        Ok(())
+    }
 }
@@ -0,0 +1,139 @@
+use crate::prelude::{DraxResult, PacketComponent, Size, TransportError};
+use std::ops::{Range, RangeInclusive};
+
+const fn double_size(size: Option<usize>) -> Option<usize> {
+    match size {
+        Some(size) => size.checked_mul(2),
+        None => None,
+    }
+}
+
+fn ordered<T: PartialOrd>(start: &T, end: &T) -> DraxResult<()> {
+    if start > end {
+        return TransportError::invalid_range();
+    }
+    Ok(())
+}
+
+/// Ranges are encoded as their start followed by their end.
+///
+/// A range whose start is greater than its end fails with `TransportError::InvalidRange`, both
+/// when decoding and encoding.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<std::ops::Range<VarInt>>(&(1..300)).await?;
+/// assert_eq!(cursor.get_ref(), &vec![1, 172, 2]);
+/// # Ok(())
+/// # }
+/// ```
+impl<C: Send + Sync, T> PacketComponent<C> for Range<T>
+where
+    T: PacketComponent<C>,
+    T::ComponentType: PartialOrd,
+{
+    type ComponentType = Range<T::ComponentType>;
+
+    const FIXED_SIZE: Option<usize> = double_size(T::FIXED_SIZE);
+
+    decode!(read, context {
+        let start = T::decode(context, read).await?;
+        let end = T::decode(context, read).await?;
+        ordered(&start, &end)?;
+        Ok(start..end)
+    });
+
+    encode!(component_ref, write, context {
+        ordered(&component_ref.start, &component_ref.end)?;
+        T::encode(&component_ref.start, context, write).await?;
+        T::encode(&component_ref.end, context, write).await?;
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        T::size(&input.start, context)?.checked_add(T::size(&input.end, context)?)
+    }
+}
+
+impl<C: Send + Sync, T> PacketComponent<C> for RangeInclusive<T>
+where
+    T: PacketComponent<C>,
+    T::ComponentType: PartialOrd,
+{
+    type ComponentType = RangeInclusive<T::ComponentType>;
+
+    const FIXED_SIZE: Option<usize> = double_size(T::FIXED_SIZE);
+
+    decode!(read, context {
+        let start = T::decode(context, read).await?;
+        let end = T::decode(context, read).await?;
+        ordered(&start, &end)?;
+        Ok(start..=end)
+    });
+
+    encode!(component_ref, write, context {
+        ordered(component_ref.start(), component_ref.end())?;
+        T::encode(component_ref.start(), context, write).await?;
+        T::encode(component_ref.end(), context, write).await?;
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        T::size(input.start(), context)?.checked_add(T::size(input.end(), context)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{
+        DraxReadExt, DraxResult, DraxWriteExt, PacketComponent, Size, TransportError, VarInt,
+    };
+    use std::assert_matches::assert_matches;
+    use std::io::Cursor;
+    use std::ops::{Range, RangeInclusive};
+
+    #[tokio::test]
+    pub async fn test_range_round_trip() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<Range<i32>>(&(-5..10)).await?;
+        cursor.encode_component::<Range<i32>>(&(3..3)).await?;
+        cursor
+            .encode_component::<RangeInclusive<VarInt>>(&(1..=300))
+            .await?;
+        assert_eq!(&cursor.get_ref()[..8], &[255, 255, 255, 251, 0, 0, 0, 10]);
+        assert_eq!(<Range<i32> as PacketComponent<()>>::FIXED_SIZE, Some(8));
+        assert_eq!(Range::<i32>::size(&(0..1), &mut ())?, Size::Constant(8));
+        assert_eq!(
+            RangeInclusive::<VarInt>::size(&(1..=300), &mut ())?,
+            Size::Dynamic(3)
+        );
+
+        cursor.set_position(0);
+        assert_eq!(cursor.decode_component::<Range<i32>>().await?, -5..10);
+        assert_eq!(cursor.decode_component::<Range<i32>>().await?, 3..3);
+        assert_eq!(
+            cursor.decode_component::<RangeInclusive<VarInt>>().await?,
+            1..=300
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_range_rejects_reversed() -> DraxResult<()> {
+        let mut bytes: &[u8] = &[0, 0, 0, 10, 0, 0, 0, 5];
+        let error = bytes.decode_component::<Range<i32>>().await;
+        assert_matches!(error, Err(TransportError::InvalidRange));
+
+        let mut bytes: &[u8] = &[10, 5];
+        let error = bytes.decode_component::<RangeInclusive<VarInt>>().await;
+        assert_matches!(error, Err(TransportError::InvalidRange));
+
+        let mut cursor = Cursor::new(vec![]);
+        #[allow(clippy::reversed_empty_ranges)]
+        let error = cursor.encode_component::<Range<i32>>(&(10..5)).await;
+        assert_matches!(error, Err(TransportError::InvalidRange));
+        assert!(cursor.get_ref().is_empty());
+        Ok(())
+    }
+}
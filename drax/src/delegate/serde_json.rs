@@ -1,7 +1,8 @@
 use crate::error::DraxResult;
 use crate::prelude::{PacketComponent, Size, VecU8};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
 
 /// A delegate struct which encodes and decodes a `serde::Serialize` and `serde::Deserialize` value.
 ///
@@ -59,3 +60,90 @@ where
         VecU8::size(&serde_json::to_vec(&input)?, context)
     }
 }
+
+/// A wrapper around a value of the packet component `P` which serializes and deserializes the
+/// value itself through serde, not its wire bytes.
+///
+/// This allows a decoded packet to be persisted as JSON or any other serde format, for example
+/// while debugging. The component type of `P` must implement `Serialize` and `Deserialize`.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![172, 2]);
+/// let value = DraxSerde::<VarInt>::new(cursor.decode_component::<VarInt>().await?);
+/// assert_eq!(serde_json::to_string(&value)?, "300");
+/// # Ok(())
+/// # }
+/// ```
+pub struct DraxSerde<P: PacketComponent<()>>(pub P::ComponentType);
+
+impl<P: PacketComponent<()>> DraxSerde<P> {
+    pub fn new(value: P::ComponentType) -> Self {
+        Self(value)
+    }
+
+    pub fn into_inner(self) -> P::ComponentType {
+        self.0
+    }
+}
+
+impl<P: PacketComponent<()>> Deref for DraxSerde<P> {
+    type Target = P::ComponentType;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<P: PacketComponent<()>> DerefMut for DraxSerde<P> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<P: PacketComponent<()>> Serialize for DraxSerde<P>
+where
+    P::ComponentType: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, P: PacketComponent<()>> Deserialize<'de> for DraxSerde<P>
+where
+    P::ComponentType: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        P::ComponentType::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::delegate::serde_json::DraxSerde;
+    use crate::prelude::{DraxReadExt, DraxResult, DraxWriteExt, Maybe, VarInt};
+    use std::collections::HashMap;
+    use std::io::Cursor;
+
+    type Scores = HashMap<String, Maybe<VarInt>>;
+
+    #[tokio::test]
+    pub async fn test_drax_serde_round_trip() -> DraxResult<()> {
+        let scores = HashMap::from([
+            ("first".to_string(), Some(300)),
+            ("second".to_string(), None),
+        ]);
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<Scores>(&scores).await?;
+        cursor.set_position(0);
+
+        let decoded = DraxSerde::<Scores>::new(cursor.decode_component::<Scores>().await?);
+        let json = serde_json::to_string(&decoded)?;
+        let back: DraxSerde<Scores> = serde_json::from_str(&json)?;
+        assert_eq!(back.into_inner(), scores);
+        Ok(())
+    }
+}
@@ -1,7 +1,8 @@
-use crate::delegate::primitive::size_var_int;
+use crate::delegate::primitive::{len_slice, size_var_int, split_slice};
 use crate::prelude::{
-    DraxReadExt, DraxResult, DraxWriteExt, PacketComponent, Size, TransportError,
+    DecodeSlice, DraxReadExt, DraxResult, DraxWriteExt, PacketComponent, Size, TransportError,
 };
+use std::marker::PhantomData;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 const STRING_DEFAULT_CAP: i32 = 32767 * 4;
@@ -14,8 +15,12 @@ impl<C: Send + Sync> PacketComponent<C> for String {
         if len > STRING_DEFAULT_CAP {
             return TransportError::limit_exceeded(STRING_DEFAULT_CAP, len, "decoding string");
         }
-        let mut buf = vec![0; len as usize];
-        read.read_exact(&mut buf).await?;
+        if len < 0 {
+            return TransportError::limit_exceeded(0, len, "decoding string");
+        }
+        let buf = read
+            .read_exact_limited(len as usize, STRING_DEFAULT_CAP as usize)
+            .await?;
         Ok(String::from_utf8(buf)?)
     });
 
@@ -36,6 +41,23 @@ impl<C: Send + Sync> PacketComponent<C> for String {
     }
 }
 
+impl DecodeSlice for String {
+    fn decode_slice(bytes: &[u8]) -> DraxResult<(Self, &[u8])> {
+        let (len, rest) = len_slice(bytes, "decoding string")?;
+        if len > STRING_DEFAULT_CAP as usize {
+            return TransportError::limit_exceeded(
+                STRING_DEFAULT_CAP,
+                len.min(i32::MAX as usize) as i32,
+                "decoding string",
+            );
+        }
+        let (value, rest) = split_slice(rest, len)?;
+        Ok((std::str::from_utf8(value)?.to_string(), rest))
+    }
+}
+
+shared_without_context!(String);
+
 /// A delegate struct which constricts the size of a `String` to the given constant limit.
 pub struct LimitedString<const N: i32>;
 
@@ -48,9 +70,13 @@ impl<C: Send + Sync, const N: i32> PacketComponent<C> for LimitedString<N> {
         if string_size > N {
             return TransportError::limit_exceeded(N, string_size, "decoding string");
         }
+        if string_size < 0 {
+            return TransportError::limit_exceeded(0, string_size, "decoding string");
+        }
 
-        let mut buf = vec![0; string_size as usize];
-        read.read_exact(&mut buf).await?;
+        let buf = read
+            .read_exact_limited(string_size as usize, N as usize)
+            .await?;
         Ok(String::from_utf8(buf)?)
     });
 
@@ -69,9 +95,316 @@ impl<C: Send + Sync, const N: i32> PacketComponent<C> for LimitedString<N> {
     }
 }
 
+const UTF8_CHUNK_SIZE: usize = 4096;
+
+/// A delegate struct which constricts the size of a `String` to the given constant limit,
+/// validating the UTF-8 as the bytes arrive.
+///
+/// The string is read in chunks and each chunk is validated before the next is read, a
+/// malformed string fails as soon as the invalid bytes are read rather than after the whole
+/// declared length. The buffer also only grows as bytes arrive, a declared length which is
+/// never sent does not allocate up front.
+pub struct IncrementalString<const N: i32>;
+
+impl<C: Send + Sync, const N: i32> PacketComponent<C> for IncrementalString<N> {
+    type ComponentType = String;
+
+    decode!(read {
+        let string_size = read.read_var_int().await?;
+
+        if string_size > N {
+            return TransportError::limit_exceeded(N, string_size, "decoding string");
+        }
+        if string_size < 0 {
+            return TransportError::limit_exceeded(0, string_size, "decoding string");
+        }
+
+        let len = string_size as usize;
+        let mut buf = Vec::with_capacity(len.min(UTF8_CHUNK_SIZE));
+        let mut valid = 0;
+        while buf.len() < len {
+            let start = buf.len();
+            let chunk = (len - start).min(UTF8_CHUNK_SIZE);
+            buf.resize(start + chunk, 0);
+            read.read_exact(&mut buf[start..]).await?;
+
+            match std::str::from_utf8(&buf[valid..]) {
+                Ok(_) => valid = buf.len(),
+                // The chunk ended part way through a character, the rest arrives with the next
+                // chunk.
+                Err(error) if error.error_len().is_none() && buf.len() < len => {
+                    valid += error.valid_up_to();
+                }
+                Err(error) => return Err(error.into()),
+            }
+        }
+
+        // SAFETY: every byte of the buffer was validated as UTF-8 above.
+        Ok(unsafe { String::from_utf8_unchecked(buf) })
+    });
+
+    encode!(component_ref, write, context {
+        LimitedString::<N>::encode(component_ref, context, write).await?
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        LimitedString::<N>::size(input, context)
+    }
+}
+
+/// A delegate struct which encodes and decodes a `String` terminated by the `DELIM` byte, such
+/// as a newline delimited line, constricted to `N` bytes excluding the delimiter.
+///
+/// Decoding reads up to and including the delimiter, which is not part of the string. The
+/// reader is consumed a byte at a time, wrap it in a buffered reader when reading many lines.
+/// Encoding a string which contains the delimiter fails with
+/// `TransportError::TerminatorCollision`.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(b"JOIN #drax\nPART".to_vec());
+/// let line = cursor.decode_component::<DelimitedString<b'\n', 512>>().await?;
+/// assert_eq!(line, "JOIN #drax");
+/// # Ok(())
+/// # }
+/// ```
+pub struct DelimitedString<const DELIM: u8, const N: usize>;
+
+impl<C: Send + Sync, const DELIM: u8, const N: usize> PacketComponent<C>
+    for DelimitedString<DELIM, N>
+{
+    type ComponentType = String;
+
+    decode!(read {
+        let mut buf = Vec::new();
+        loop {
+            let byte = read.read_u8().await?;
+            if byte == DELIM {
+                break;
+            }
+            if buf.len() == N {
+                return TransportError::limit_exceeded(
+                    N.min(i32::MAX as usize) as i32,
+                    (N + 1).min(i32::MAX as usize) as i32,
+                    "decoding delimited string",
+                );
+            }
+            buf.push(byte);
+        }
+        Ok(String::from_utf8(buf)?)
+    });
+
+    encode!(component_ref, write {
+        if component_ref.len() > N {
+            return TransportError::limit_exceeded(
+                N.min(i32::MAX as usize) as i32,
+                component_ref.len().min(i32::MAX as usize) as i32,
+                "encoding delimited string",
+            );
+        }
+        if component_ref.as_bytes().contains(&DELIM) {
+            return TransportError::terminator_collision(DELIM);
+        }
+        write.write_all(component_ref.as_bytes()).await?;
+        write.write_u8(DELIM).await?;
+    });
+
+    fn size(input: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+        Ok(Size::Dynamic(input.len() + 1))
+    }
+}
+
+/// Defines an integer type which can carry the length prefix of a [`PrefixedString`].
+pub trait PrefixLength: Copy {
+    /// The largest length the type can carry.
+    const MAX: i128;
+
+    fn from_len(len: usize) -> Option<Self>;
+
+    fn into_i128(self) -> i128;
+}
+
+macro_rules! define_prefix_length {
+    ($($prim:ty),*) => {$(
+        impl PrefixLength for $prim {
+            const MAX: i128 = <$prim>::MAX as i128;
+
+            fn from_len(len: usize) -> Option<Self> {
+                <$prim>::try_from(len).ok()
+            }
+
+            fn into_i128(self) -> i128 {
+                self as i128
+            }
+        }
+    )*};
+}
+
+define_prefix_length!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+fn clamp_i128(value: i128) -> i32 {
+    value.clamp(i32::MIN as i128, i32::MAX as i128) as i32
+}
+
+/// A delegate struct which encodes and decodes a `String` prefixed by its length in bytes
+/// encoded as the component `L`, such as `u16` or `VarInt`.
+///
+/// Lengths which do not fit `L` are rejected with `TransportError::LimitExceeded` when encoding.
+/// Decoded lengths are checked against the same cap as `String` before anything is allocated.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<PrefixedString<u16>>(&"drax".to_string()).await?;
+/// assert_eq!(cursor.get_ref(), &vec![0, 4, b'd', b'r', b'a', b'x']);
+/// # Ok(())
+/// # }
+/// ```
+pub struct PrefixedString<L>(PhantomData<L>);
+
+impl<C: Send + Sync, L> PacketComponent<C> for PrefixedString<L>
+where
+    L: PacketComponent<C>,
+    L::ComponentType: PrefixLength,
+{
+    type ComponentType = String;
+
+    decode!(read, context {
+        let len = L::decode(context, read).await?.into_i128();
+        if len < 0 {
+            return TransportError::limit_exceeded(0, clamp_i128(len), "decoding prefixed string");
+        }
+        if len > STRING_DEFAULT_CAP as i128 {
+            return TransportError::limit_exceeded(
+                STRING_DEFAULT_CAP,
+                clamp_i128(len),
+                "decoding prefixed string",
+            );
+        }
+        let buf = read
+            .read_exact_limited(len as usize, STRING_DEFAULT_CAP as usize)
+            .await?;
+        Ok(String::from_utf8(buf)?)
+    });
+
+    encode!(component_ref, write, context {
+        let Some(len) = L::ComponentType::from_len(component_ref.len()) else {
+            return TransportError::limit_exceeded(
+                clamp_i128(L::ComponentType::MAX),
+                component_ref.len().min(i32::MAX as usize) as i32,
+                "encoding prefixed string",
+            );
+        };
+        L::encode(&len, context, write).await?;
+        write.write_all(component_ref.as_bytes()).await?;
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        let Some(len) = L::ComponentType::from_len(input.len()) else {
+            return TransportError::limit_exceeded(
+                clamp_i128(L::ComponentType::MAX),
+                input.len().min(i32::MAX as usize) as i32,
+                "encoding prefixed string",
+            );
+        };
+        Ok(Size::Dynamic(L::size(&len, context)?.bytes() + input.len()))
+    }
+}
+
+/// A delegate which encodes and decodes a `String` prefixed by its length in bytes as a
+/// big-endian `u16`, as used by legacy protocols.
+///
+/// The layout is `[len: u16 BE][len bytes of UTF-8]`, so a string is at most 65535 bytes long.
+/// Encoding a longer string fails with `TransportError::LimitExceeded`. This is
+/// [`PrefixedString<u16>`](PrefixedString) under its conventional name.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<ShortString>(&"hi".to_string()).await?;
+/// assert_eq!(cursor.get_ref(), &vec![0x00, 0x02, b'h', b'i']);
+/// # Ok(())
+/// # }
+/// ```
+pub type ShortString = PrefixedString<u16>;
+
+/// Defines a context which carries a scratch buffer reused when decoding strings.
+pub trait HasStringScratch {
+    fn string_scratch(&mut self) -> &mut Vec<u8>;
+}
+
+impl HasStringScratch for Vec<u8> {
+    fn string_scratch(&mut self) -> &mut Vec<u8> {
+        self
+    }
+}
+
+/// A delegate struct which constricts the size of a `String` to the given constant limit,
+/// reading the raw bytes into the scratch buffer of the context.
+///
+/// The bytes are validated in place and only the resulting string is allocated, the scratch
+/// buffer keeps its capacity between strings. This is otherwise identical to `LimitedString`.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use drax::delegate::string::ScratchString;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<String>(&"test string".to_string()).await?;
+/// cursor.set_position(0);
+///
+/// let mut scratch = Vec::with_capacity(256);
+/// let back = ScratchString::<256>::decode(&mut scratch, &mut cursor).await?;
+/// assert_eq!(back, "test string");
+/// # Ok(())
+/// # }
+/// ```
+pub struct ScratchString<const N: i32>;
+
+impl<C: HasStringScratch + Send + Sync, const N: i32> PacketComponent<C> for ScratchString<N> {
+    type ComponentType = String;
+
+    decode!(read, context {
+        let string_size = read.read_var_int().await?;
+
+        if string_size > N {
+            return TransportError::limit_exceeded(N, string_size, "decoding string");
+        }
+        if string_size < 0 {
+            return TransportError::limit_exceeded(0, string_size, "decoding string");
+        }
+
+        let scratch = context.string_scratch();
+        scratch.clear();
+        scratch.resize(string_size as usize, 0);
+        read.read_exact(scratch).await?;
+        Ok(std::str::from_utf8(scratch)?.to_owned())
+    });
+
+    encode!(component_ref, write, context {
+        LimitedString::<N>::encode(component_ref, context, write).await?
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        LimitedString::<N>::size(input, context)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::prelude::{DraxReadExt, DraxResult, DraxWriteExt, LimitedString, TransportError};
+    use crate::delegate::string::{IncrementalString, ScratchString, UTF8_CHUNK_SIZE};
+    use crate::io::CountingReader;
+    use crate::prelude::{
+        DelimitedString, DraxReadExt, DraxResult, DraxWriteExt, LimitedString, PacketComponent,
+        PrefixedString, ShortString, Size, TransportError, VarInt,
+    };
     use std::assert_matches::assert_matches;
     use std::io::Cursor;
 
@@ -119,4 +452,228 @@ mod test {
         );
         Ok(())
     }
+
+    #[tokio::test]
+    pub async fn test_scratch_string_decoding() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<String>(&"first".to_string())
+            .await?;
+        cursor
+            .encode_component::<String>(&"second string".to_string())
+            .await?;
+        cursor
+            .encode_component::<String>(&"third".to_string())
+            .await?;
+
+        cursor.set_position(0);
+        let mut scratch = vec![];
+        for expected in ["first", "second string", "third"] {
+            let back = ScratchString::<32>::decode(&mut scratch, &mut cursor).await?;
+            assert_eq!(back, expected);
+        }
+        assert!(scratch.capacity() >= 13);
+
+        cursor.set_position(0);
+        for expected in ["first", "second string", "third"] {
+            let back = cursor.decode_component::<LimitedString<32>>().await?;
+            assert_eq!(back, expected);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_scratch_string_errors() -> DraxResult<()> {
+        let mut scratch = vec![];
+
+        let mut cursor = Cursor::new(vec![2, 0xC3, 0x28]);
+        let error = ScratchString::<32>::decode(&mut scratch, &mut cursor).await;
+        assert_matches!(error, Err(TransportError::InvalidUtf8(_)));
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(33).await?;
+        cursor.set_position(0);
+        let error = ScratchString::<32>::decode(&mut scratch, &mut cursor).await;
+        assert_matches!(
+            error,
+            Err(TransportError::LimitExceeded(32, 33, "decoding string"))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_incremental_string_decoding() -> DraxResult<()> {
+        let expected = "\u{1F600}drax".repeat(UTF8_CHUNK_SIZE / 3);
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<String>(&expected).await?;
+        cursor.set_position(0);
+
+        let back = cursor
+            .decode_component::<IncrementalString<{ i32::MAX }>>()
+            .await?;
+        assert_eq!(back, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_incremental_string_fails_fast() -> DraxResult<()> {
+        let declared = 8 * 1024 * 1024;
+        let mut bytes = Cursor::new(vec![]);
+        bytes.write_var_int(declared).await?;
+        let mut bytes = bytes.into_inner();
+        bytes.push(0xFF);
+        bytes.resize(bytes.len() + declared as usize - 1, b'a');
+
+        let mut reader = CountingReader::new(Cursor::new(bytes));
+        let error = reader
+            .decode_component::<IncrementalString<{ i32::MAX }>>()
+            .await;
+
+        assert_matches!(error, Err(TransportError::InvalidUtf8(_)));
+        assert!(reader.count() <= (UTF8_CHUNK_SIZE + 4) as u64);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_incremental_string_truncated_character() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![2, 0xF0, 0x9F]);
+        let error = cursor.decode_component::<IncrementalString<32>>().await;
+        assert_matches!(error, Err(TransportError::InvalidUtf8(_)));
+        Ok(())
+    }
+
+    type Line = DelimitedString<b'\n', 16>;
+
+    #[tokio::test]
+    pub async fn test_delimited_string_line() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<Line>(&"PING drax".to_string())
+            .await?;
+        cursor.encode_component::<Line>(&"".to_string()).await?;
+        assert_eq!(cursor.get_ref(), b"PING drax\n\n");
+        assert_eq!(
+            Line::size(&"PING drax".to_string(), &mut ())?,
+            Size::Dynamic(10)
+        );
+
+        cursor.set_position(0);
+        assert_eq!(cursor.decode_component::<Line>().await?, "PING drax");
+        assert_eq!(cursor.decode_component::<Line>().await?, "");
+        assert_eq!(cursor.position(), 11);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_delimited_string_over_length() -> DraxResult<()> {
+        let mut cursor = Cursor::new(b"0123456789abcdef\n".to_vec());
+        assert_eq!(cursor.decode_component::<Line>().await?.len(), 16);
+
+        let mut cursor = Cursor::new(b"0123456789abcdefg\n".to_vec());
+        let error = cursor.decode_component::<Line>().await;
+        assert_matches!(
+            error,
+            Err(TransportError::LimitExceeded(
+                16,
+                17,
+                "decoding delimited string"
+            ))
+        );
+        assert_eq!(cursor.position(), 17);
+
+        let error = Cursor::new(vec![])
+            .encode_component::<Line>(&"0123456789abcdefg".to_string())
+            .await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(16, 17, _)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_delimited_string_interior_delimiter() -> DraxResult<()> {
+        let error = Cursor::new(vec![])
+            .encode_component::<Line>(&"PING\nPONG".to_string())
+            .await;
+        assert_matches!(error, Err(TransportError::TerminatorCollision(b'\n')));
+
+        let mut cursor = Cursor::new(b"unterminated".to_vec());
+        let error = cursor.decode_component::<Line>().await;
+        assert!(error.is_err_and(|error| error.is_eof()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_prefixed_string_round_trip() -> DraxResult<()> {
+        let value = "prefixed".to_string();
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<PrefixedString<u16>>(&value)
+            .await?;
+        cursor
+            .encode_component::<PrefixedString<u8>>(&value)
+            .await?;
+        cursor
+            .encode_component::<PrefixedString<VarInt>>(&value)
+            .await?;
+        assert_eq!(cursor.get_ref().len(), 10 + 9 + 9);
+        assert_eq!(
+            PrefixedString::<u16>::size(&value, &mut ())?,
+            Size::Dynamic(10)
+        );
+
+        cursor.set_position(0);
+        assert_eq!(
+            cursor.decode_component::<PrefixedString<u16>>().await?,
+            value
+        );
+        assert_eq!(
+            cursor.decode_component::<PrefixedString<u8>>().await?,
+            value
+        );
+        assert_eq!(
+            cursor.decode_component::<PrefixedString<VarInt>>().await?,
+            value
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_prefixed_string_overflow() -> DraxResult<()> {
+        let value = "a".repeat(300);
+        let mut cursor = Cursor::new(vec![]);
+        let error = cursor.encode_component::<PrefixedString<u8>>(&value).await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(255, 300, _)));
+        assert!(cursor.get_ref().is_empty());
+
+        let mut bytes: &[u8] = &[0xFF, 0xFF, 0xFF, 0xFF, 0x0F];
+        let error = bytes.decode_component::<PrefixedString<VarInt>>().await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(0, -1, _)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_short_string() -> DraxResult<()> {
+        let value = "é".repeat(200);
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<ShortString>(&value).await?;
+        assert_eq!(&cursor.get_ref()[..2], &[0x01, 0x90]);
+        assert_eq!(&cursor.get_ref()[2..], value.as_bytes());
+        assert_eq!(ShortString::size(&value, &mut ())?, Size::Dynamic(402));
+
+        cursor.set_position(0);
+        assert_eq!(cursor.decode_component::<ShortString>().await?, value);
+
+        let max = "a".repeat(u16::MAX as usize);
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<ShortString>(&max).await?;
+        cursor.set_position(0);
+        assert_eq!(cursor.decode_component::<ShortString>().await?, max);
+
+        let mut cursor = Cursor::new(vec![]);
+        let error = cursor
+            .encode_component::<ShortString>(&"a".repeat(65536))
+            .await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(65535, 65536, _)));
+        assert!(cursor.get_ref().is_empty());
+        Ok(())
+    }
 }
@@ -1,15 +1,19 @@
-use crate::delegate::primitive::size_var_int;
+use crate::delegate::guard::UNTRUSTED_CAPACITY;
+use crate::delegate::primitive::{len_slice, size_var_int, split_slice};
 use crate::prelude::{
-    DraxReadExt, DraxResult, DraxWriteExt, PacketComponent, Size, TransportError,
+    DecodeSlice, DraxReadExt, DraxResult, DraxWriteExt, PacketComponent, Size, TransportError,
 };
 use std::marker::PhantomData;
 use std::mem::MaybeUninit;
+use std::sync::Arc;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 /// A delegate struct which encodes and decodes a `Vec<u8>` type.
 ///
 /// This delegate instructs the reader to read the entirety of the remaining bytes
-/// into the `Vec<u8>` type.
+/// into the `Vec<u8>` type. On a live socket this reads until the connection closes, so it
+/// should only be used on readers which are already bounded such as a decoded frame, prefer
+/// [`LimitedByteDrain`] otherwise.
 ///
 /// ```rust
 /// # use drax::prelude::*;
@@ -43,18 +47,60 @@ impl<C: Send + Sync> PacketComponent<C> for ByteDrain {
     }
 }
 
+/// A delegate struct which reads the remaining bytes like [`ByteDrain`], reading at most `N`
+/// bytes.
+///
+/// A reader with more than `N` bytes remaining is rejected with `TransportError::LimitExceeded`
+/// after reading `N + 1` bytes, the rest of the reader is left untouched.
+pub struct LimitedByteDrain<const N: usize>;
+
+impl<C: Send + Sync, const N: usize> PacketComponent<C> for LimitedByteDrain<N> {
+    type ComponentType = Vec<u8>;
+
+    decode!(read {
+        let mut bytes = vec![];
+        (&mut *read)
+            .take(N as u64 + 1)
+            .read_to_end(&mut bytes)
+            .await?;
+        if bytes.len() > N {
+            return TransportError::limit_exceeded(
+                i32::try_from(N).unwrap_or(i32::MAX),
+                i32::try_from(bytes.len()).unwrap_or(i32::MAX),
+                "decoding byte drain",
+            );
+        }
+        Ok(bytes)
+    });
+
+    encode!(component_ref, write {
+        if component_ref.len() > N {
+            return TransportError::limit_exceeded(
+                i32::try_from(N).unwrap_or(i32::MAX),
+                i32::try_from(component_ref.len()).unwrap_or(i32::MAX),
+                "encoding byte drain",
+            );
+        }
+        write.write_all(component_ref).await?;
+    });
+
+    fn size(component_ref: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+        Ok(Size::Dynamic(component_ref.len()))
+    }
+}
+
 /// A delegate struct which encodes and decodes a `[N; u8]` type.
 ///
 /// This differs from the `[T; N]` implementation in that it optimizes the
 /// read and write operations since the length is also the remaining bytes
-/// to be read.
-#[cfg(feature = "slices")]
+/// to be read. Unlike `[T; N]` it does not require the `slices` feature.
 pub struct SliceU8<const N: usize>;
 
-#[cfg(feature = "slices")]
 impl<C: Send + Sync, const N: usize> PacketComponent<C> for SliceU8<N> {
     type ComponentType = [u8; N];
 
+    const FIXED_SIZE: Option<usize> = Some(N);
+
     decode!(read {
         let mut buf = [0; N];
         read.read_exact(&mut buf).await?;
@@ -77,11 +123,24 @@ where
 {
     type ComponentType = [T::ComponentType; N];
 
+    const FIXED_SIZE: Option<usize> = match T::FIXED_SIZE {
+        Some(size) => size.checked_mul(N),
+        None => None,
+    };
+
     decode!(read, context {
-        let mut arr: [MaybeUninit<T::ComponentType>; N] = MaybeUninit::uninit_array();
-        for i in &mut arr {
-            *i = MaybeUninit::new(T::decode(context, read).await?);
+        let mut arr: [MaybeUninit<T::ComponentType>; N] = [const { MaybeUninit::uninit() }; N];
+        let mut partial = PartialInit {
+            elements: &mut arr,
+            initialized: 0,
+        };
+        while partial.initialized < N {
+            let element = T::decode(context, read).await?;
+            partial.elements[partial.initialized].write(element);
+            partial.initialized += 1;
         }
+        std::mem::forget(partial);
+        // SAFETY: every element was written by the loop above.
         Ok(arr.map(|x| unsafe { x.assume_init() }))
     });
 
@@ -92,21 +151,24 @@ where
     });
 
     fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
-        let mut dynamic_counter = 0;
+        if let Some(size) = Self::FIXED_SIZE {
+            return Ok(Size::Constant(size));
+        }
+        // Elements are summed one by one, a constant size reported for one element says
+        // nothing about the others unless the component declares a fixed size.
+        let mut size = Size::Constant(0);
         for item in component_ref {
-            match T::size(item, context)? {
-                Size::Constant(x) => return Ok(Size::Constant(x * N)),
-                Size::Dynamic(x) => dynamic_counter += x,
-            }
+            size = size.checked_add(T::size(item, context)?)?;
         }
-        Ok(Size::Dynamic(dynamic_counter))
+        Ok(size)
     }
 }
 
 /// A delegate struct which encodes and decodes a `Vec<u8>` type.
 ///
 /// Similar to the `SliceU8` delegate, this optimizes the read and write operations
-/// since the length is also the remaining bytes to be read.
+/// since the length is also the remaining bytes to be read. Any length up to `i32::MAX` is
+/// accepted, use `LimitedVecU8` to bound lengths taken from untrusted peers.
 pub struct VecU8;
 
 impl<C: Send + Sync> PacketComponent<C> for VecU8 {
@@ -114,9 +176,10 @@ impl<C: Send + Sync> PacketComponent<C> for VecU8 {
 
     decode!(read {
         let len = read.read_var_int().await?;
-        let mut buf = vec![0u8; len as usize];
-        read.read_exact(&mut buf).await?;
-        Ok(buf)
+        if len < 0 {
+            return TransportError::limit_exceeded(0, len, "decoding byte vec");
+        }
+        read.read_exact_limited(len as usize, i32::MAX as usize).await
     });
 
     encode!(component_ref, write {
@@ -124,47 +187,609 @@ impl<C: Send + Sync> PacketComponent<C> for VecU8 {
         write.write_all(component_ref).await?;
     });
 
-    fn size(component_ref: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
-        Ok(Size::Dynamic(
-            component_ref.len() + size_var_int(component_ref.len() as i32),
-        ))
+    fn size(component_ref: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+        Ok(Size::Dynamic(
+            component_ref.len() + size_var_int(component_ref.len() as i32),
+        ))
+    }
+}
+
+impl DecodeSlice for VecU8 {
+    fn decode_slice(bytes: &[u8]) -> DraxResult<(Vec<u8>, &[u8])> {
+        let (len, rest) = len_slice(bytes, "decoding byte vec")?;
+        let (value, rest) = split_slice(rest, len)?;
+        Ok((value.to_vec(), rest))
+    }
+}
+
+/// A delegate struct which limits the size of a `Vec<u8>` when encoding/decoding to the
+/// given constant limit.
+///
+/// The length is validated before the buffer is allocated, making this the preferred delegate
+/// for binary blobs received from untrusted sources.
+pub struct LimitedVecU8<const N: usize>;
+
+impl<C: Send + Sync, const N: usize> PacketComponent<C> for LimitedVecU8<N> {
+    type ComponentType = Vec<u8>;
+
+    decode!(read {
+        let len = read.read_var_int().await?;
+        let lim = N as i32;
+        if len > lim {
+            return TransportError::limit_exceeded(lim, len, "decoding byte vec");
+        }
+        if len < 0 {
+            return TransportError::limit_exceeded(0, len, "decoding byte vec");
+        }
+
+        read.read_exact_limited(len as usize, N).await
+    });
+
+    encode!(component_ref, write, context {
+        let len = component_ref.len() as i32;
+        let lim = N as i32;
+
+        if len > lim {
+            return TransportError::limit_exceeded(lim, len, "encoding byte vec");
+        }
+
+        VecU8::encode(component_ref, context, write).await?;
+    });
+
+    fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        VecU8::size(component_ref, context)
+    }
+}
+
+/// A delegate struct which encodes and decodes a `Vec<u8>` as runs of repeated bytes, the
+/// expanded length limited to the given constant.
+///
+/// The runs are prefixed by their VarInt count, each run is laid out as
+/// `[VarInt repeat][u8 byte]`. The expanded length is accounted as each run is read, a small
+/// payload declaring runs which expand past `N` is rejected before the bytes are allocated.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<RleBytes<1024>>(&vec![0; 300]).await?;
+/// assert_eq!(cursor.get_ref(), &vec![1, 172, 2, 0]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct RleBytes<const N: usize>;
+
+impl<const N: usize> RleBytes<N> {
+    const LIMIT: i32 = if N > i32::MAX as usize {
+        i32::MAX
+    } else {
+        N as i32
+    };
+
+    fn runs(bytes: &[u8]) -> impl Iterator<Item = (i32, u8)> + '_ {
+        bytes
+            .chunk_by(|first, second| first == second)
+            .map(|run| (run.len() as i32, run[0]))
+    }
+}
+
+impl<C: Send + Sync, const N: usize> PacketComponent<C> for RleBytes<N> {
+    type ComponentType = Vec<u8>;
+
+    decode!(read {
+        let runs = read.read_var_int().await?;
+        if runs < 0 {
+            return TransportError::limit_exceeded(0, runs, "decoding run count");
+        }
+
+        let lim = Self::LIMIT;
+        let mut bytes = Vec::new();
+        for _ in 0..runs {
+            let repeat = read.read_var_int().await?;
+            if repeat < 0 {
+                return TransportError::limit_exceeded(0, repeat, "decoding run length");
+            }
+            let len = (bytes.len() as i32).saturating_add(repeat);
+            if len > lim {
+                return TransportError::limit_exceeded(lim, len, "decoding run-length bytes");
+            }
+            let byte = read.read_u8().await?;
+            bytes.resize(len as usize, byte);
+        }
+        Ok(bytes)
+    });
+
+    encode!(component_ref, write {
+        if component_ref.len() > Self::LIMIT as usize {
+            return TransportError::limit_exceeded(
+                Self::LIMIT,
+                component_ref.len().min(i32::MAX as usize) as i32,
+                "encoding run-length bytes",
+            );
+        }
+
+        write.write_var_int(Self::runs(component_ref).count() as i32).await?;
+        for (repeat, byte) in Self::runs(component_ref) {
+            write.write_var_int(repeat).await?;
+            write.write_u8(byte).await?;
+        }
+    });
+
+    fn size(component_ref: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+        let mut runs = 0;
+        let mut size = 0usize;
+        for (repeat, _) in Self::runs(component_ref) {
+            runs += 1;
+            size = size.saturating_add(size_var_int(repeat) + 1);
+        }
+        Ok(Size::Dynamic(size.saturating_add(size_var_int(runs))))
+    }
+}
+
+impl<C: Send + Sync, T> PacketComponent<C> for Vec<T>
+where
+    T: PacketComponent<C>,
+{
+    type ComponentType = Vec<T::ComponentType>;
+
+    decode!(read, context {
+        let len = read.read_var_int().await?;
+        let mut vec = Vec::with_capacity(len as usize);
+        for _ in 0..len {
+            vec.push(T::decode(context, read).await?);
+        }
+        Ok(vec)
+    });
+
+    encode!(component_ref, write, context {
+        write.write_var_int(component_ref.len() as i32).await?;
+        for item in component_ref {
+            T::encode(item, context, write).await?;
+        }
+    });
+
+    fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        size_length_prefixed::<C, T>(component_ref, context)
+    }
+}
+
+/// Calculates the size of the elements prefixed by their VarInt count.
+///
+/// Elements are sized one by one unless `T` declares a fixed size, a constant size reported
+/// for one element says nothing about the size of the others.
+fn size_length_prefixed<C: Send + Sync, T: PacketComponent<C>>(
+    elements: &[T::ComponentType],
+    context: &mut C,
+) -> DraxResult<Size> {
+    size_counted::<C, T>(elements.len(), elements, context)
+}
+
+fn size_counted<'a, C: Send + Sync, T: PacketComponent<C>>(
+    len: usize,
+    elements: impl IntoIterator<Item = &'a T::ComponentType>,
+    context: &mut C,
+) -> DraxResult<Size>
+where
+    T::ComponentType: 'a,
+{
+    let var_int_size = size_var_int(len as i32);
+    if let Some(size) = T::FIXED_SIZE {
+        return match size
+            .checked_mul(len)
+            .and_then(|size| size.checked_add(var_int_size))
+        {
+            Some(size) => Ok(Size::Dynamic(size)),
+            None => TransportError::size_overflow(),
+        };
+    }
+
+    let mut size = Size::Dynamic(var_int_size);
+    for item in elements {
+        size = size.checked_add(T::size(item, context)?)?;
+    }
+    Ok(size)
+}
+
+/// Encodes and decodes a `SmallVec` in the same format as a `Vec`, a VarInt length followed
+/// by the elements. Collections of up to `N` elements are decoded without allocating.
+#[cfg(feature = "smallvec")]
+impl<C: Send + Sync, T, const N: usize> PacketComponent<C> for smallvec::SmallVec<[T; N]>
+where
+    T: PacketComponent<C>,
+{
+    type ComponentType = smallvec::SmallVec<[T::ComponentType; N]>;
+
+    decode!(read, context {
+        let len = read.read_var_int().await?;
+        if len < 0 {
+            return TransportError::limit_exceeded(0, len, "decoding small vec length");
+        }
+        let mut vec = smallvec::SmallVec::new();
+        for _ in 0..len {
+            vec.push(T::decode(context, read).await?);
+        }
+        Ok(vec)
+    });
+
+    encode!(component_ref, write, context {
+        write.write_var_int(component_ref.len() as i32).await?;
+        for item in component_ref {
+            T::encode(item, context, write).await?;
+        }
+    });
+
+    fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        size_length_prefixed::<C, T>(component_ref, context)
+    }
+}
+
+/// A delegate struct which encodes and decodes exactly `N` elements of a `Vec<T>` without a
+/// length prefix.
+///
+/// Unlike the `[T; N]` implementation this does not require the `slices` feature. Encoding a
+/// `Vec` which does not contain exactly `N` elements will fail.
+pub struct FixedVec<T, const N: usize>(PhantomData<T>);
+
+impl<C: Send + Sync, T, const N: usize> PacketComponent<C> for FixedVec<T, N>
+where
+    T: PacketComponent<C>,
+{
+    type ComponentType = Vec<T::ComponentType>;
+
+    decode!(read, context {
+        let mut vec = Vec::with_capacity(N);
+        for _ in 0..N {
+            vec.push(T::decode(context, read).await?);
+        }
+        Ok(vec)
+    });
+
+    encode!(component_ref, write, context {
+        if component_ref.len() != N {
+            return TransportError::limit_exceeded(
+                N as i32,
+                component_ref.len() as i32,
+                "encoding fixed vec",
+            );
+        }
+
+        for item in component_ref {
+            T::encode(item, context, write).await?;
+        }
+    });
+
+    fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        let mut size = Size::Constant(0);
+        for item in component_ref {
+            size = size.checked_add(T::size(item, context)?)?;
+        }
+        Ok(size)
+    }
+}
+
+/// A delegate struct which encodes and decodes two parallel vectors sharing a single length
+/// prefix.
+///
+/// The arrays are laid out as `[VarInt count][count A elements][count B elements]`, matching
+/// struct-of-arrays wire layouts. Encoding vectors of different lengths will fail.
+pub struct ParallelArrays2<A, B>(PhantomData<(A, B)>);
+
+impl<C: Send + Sync, T, U> PacketComponent<C> for ParallelArrays2<T, U>
+where
+    T: PacketComponent<C>,
+    U: PacketComponent<C>,
+{
+    type ComponentType = (Vec<T::ComponentType>, Vec<U::ComponentType>);
+
+    decode!(read, context {
+        let len = read.read_var_int().await?;
+        if len < 0 {
+            return TransportError::limit_exceeded(0, len, "decoding parallel arrays");
+        }
+
+        let mut first = Vec::with_capacity((len as usize).min(UNTRUSTED_CAPACITY));
+        for _ in 0..len {
+            first.push(T::decode(context, read).await?);
+        }
+        let mut second = Vec::with_capacity((len as usize).min(UNTRUSTED_CAPACITY));
+        for _ in 0..len {
+            second.push(U::decode(context, read).await?);
+        }
+        Ok((first, second))
+    });
+
+    encode!(component_ref, write, context {
+        let (first, second) = component_ref;
+        if first.len() != second.len() {
+            return TransportError::limit_exceeded(
+                first.len() as i32,
+                second.len() as i32,
+                "encoding parallel arrays",
+            );
+        }
+
+        write.write_var_int(first.len() as i32).await?;
+        for item in first {
+            T::encode(item, context, write).await?;
+        }
+        for item in second {
+            U::encode(item, context, write).await?;
+        }
+    });
+
+    fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        let (first, second) = component_ref;
+        let mut size = Size::Dynamic(size_var_int(first.len() as i32));
+        for item in first {
+            size = size.checked_add(T::size(item, context)?)?;
+        }
+        for item in second {
+            size = size.checked_add(U::size(item, context)?)?;
+        }
+        Ok(size)
+    }
+}
+
+/// A delegate struct which encodes and decodes a `Vec<T>` prefixed by the total length in
+/// bytes of its elements rather than the element count.
+///
+/// A reader can skip the whole block using the prefix. Elements are decoded until the block is
+/// exhausted, an element which is cut off by the end of the block fails with the error of the
+/// element decode.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<ByteLengthPrefixedVec<u16>>(&vec![1, 2]).await?;
+/// assert_eq!(cursor.get_ref(), &vec![4, 0, 1, 0, 2]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct ByteLengthPrefixedVec<T>(PhantomData<T>);
+
+impl<C: Send + Sync, T> PacketComponent<C> for ByteLengthPrefixedVec<T>
+where
+    T: PacketComponent<C>,
+{
+    type ComponentType = Vec<T::ComponentType>;
+
+    decode!(read, context {
+        let len = read.read_var_int().await?;
+        if len < 0 {
+            return TransportError::limit_exceeded(0, len, "decoding byte length");
+        }
+
+        let mut limited = (&mut *read).take(len as u64);
+        let mut vec = Vec::new();
+        while limited.limit() != 0 {
+            let remaining = limited.limit();
+            vec.push(T::decode(context, &mut limited).await?);
+            if limited.limit() == remaining {
+                // An element which consumes no bytes would never exhaust the block.
+                return TransportError::trailing_bytes(remaining);
+            }
+        }
+        Ok(vec)
+    });
+
+    encode!(component_ref, write, context {
+        let mut buffer = Vec::new();
+        for item in component_ref {
+            T::encode(item, context, &mut buffer).await?;
+        }
+        write.write_var_int(buffer.len() as i32).await?;
+        write.write_all(&buffer).await?;
+    });
+
+    fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        let mut size = Size::Constant(0);
+        for item in component_ref {
+            size = size.checked_add(T::size(item, context)?)?;
+        }
+        let body = size.as_usize();
+        Ok(Size::Dynamic(body) + size_var_int(body as i32))
+    }
+}
+
+/// A delegate struct which encodes and decodes an `Arc<[T]>` in the same format as a `Vec<T>`.
+///
+/// The elements are decoded directly into the shared allocation rather than into a `Vec` which
+/// is then copied into an `Arc`. Lengths above `UNTRUSTED_CAPACITY` are not trusted to size the
+/// allocation up front, those are decoded into a growing `Vec` and copied instead. The decoded
+/// slice can be cloned cheaply and shared across tasks, for example when broadcasting.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # use std::sync::Arc;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![2, 10, 20]);
+/// let shared: Arc<[i32]> = cursor.decode_component::<ArcSlice<VarInt>>().await?;
+/// assert_eq!(&*shared, &[10, 20]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct ArcSlice<T>(PhantomData<T>);
+
+/// Drops the initialized prefix of a partially decoded slice or array if decoding fails part
+/// way.
+struct PartialInit<'a, T> {
+    elements: &'a mut [MaybeUninit<T>],
+    initialized: usize,
+}
+
+impl<T> Drop for PartialInit<'_, T> {
+    fn drop(&mut self) {
+        for element in &mut self.elements[..self.initialized] {
+            // SAFETY: the first `initialized` elements have been written.
+            unsafe { element.assume_init_drop() };
+        }
+    }
+}
+
+impl<C: Send + Sync, T> PacketComponent<C> for ArcSlice<T>
+where
+    T: PacketComponent<C>,
+{
+    type ComponentType = Arc<[T::ComponentType]>;
+
+    decode!(read, context {
+        let len = read.read_var_int().await?;
+        if len < 0 {
+            return TransportError::limit_exceeded(0, len, "decoding arc slice");
+        }
+
+        if len as usize > UNTRUSTED_CAPACITY {
+            let mut vec = Vec::with_capacity(UNTRUSTED_CAPACITY);
+            for _ in 0..len {
+                vec.push(T::decode(context, read).await?);
+            }
+            return Ok(Arc::from(vec));
+        }
+
+        let mut slice = Arc::<[T::ComponentType]>::new_uninit_slice(len as usize);
+        let mut partial = PartialInit {
+            elements: Arc::get_mut(&mut slice).expect("a new arc is not shared"),
+            initialized: 0,
+        };
+        while partial.initialized < partial.elements.len() {
+            let element = T::decode(context, read).await?;
+            partial.elements[partial.initialized].write(element);
+            partial.initialized += 1;
+        }
+        std::mem::forget(partial);
+        // SAFETY: every element was written by the loop above.
+        Ok(unsafe { slice.assume_init() })
+    });
+
+    encode!(component_ref, write, context {
+        write.write_var_int(component_ref.len() as i32).await?;
+        for item in component_ref.iter() {
+            T::encode(item, context, write).await?;
+        }
+    });
+
+    fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        size_length_prefixed::<C, T>(component_ref, context)
+    }
+}
+
+/// A delegate struct which encodes and decodes a `Vec<T>` whose elements are followed by a
+/// terminator byte instead of being prefixed by their count.
+///
+/// When decoding the next byte is read first, the collection ends if it is `TERM`, otherwise
+/// the byte is handed back to `T` as the start of the next element. Since an element starting
+/// with `TERM` could not be told apart from the end of the collection, encoding such an element
+/// (or an element which encodes no bytes) fails with `TransportError::TerminatorCollision`.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<Terminated<VarInt, 0>>(&vec![1, 2]).await?;
+/// assert_eq!(cursor.get_ref(), &vec![1, 2, 0]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Terminated<T, const TERM: u8>(PhantomData<T>);
+
+impl<C: Send + Sync, T, const TERM: u8> PacketComponent<C> for Terminated<T, TERM>
+where
+    T: PacketComponent<C>,
+{
+    type ComponentType = Vec<T::ComponentType>;
+
+    decode!(read, context {
+        let mut vec = Vec::new();
+        loop {
+            let next = read.read_u8().await?;
+            if next == TERM {
+                return Ok(vec);
+            }
+            let first = [next];
+            let mut element = (&first[..]).chain(&mut *read);
+            vec.push(T::decode(context, &mut element).await?);
+        }
+    });
+
+    encode!(component_ref, write, context {
+        let mut buffer = Vec::new();
+        for item in component_ref {
+            buffer.clear();
+            T::encode(item, context, &mut buffer).await?;
+            if buffer.first().is_none_or(|first| *first == TERM) {
+                return TransportError::terminator_collision(TERM);
+            }
+            write.write_all(&buffer).await?;
+        }
+        write.write_u8(TERM).await?;
+    });
+
+    fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        let mut size = Size::Dynamic(1);
+        for item in component_ref {
+            size = size.checked_add(T::size(item, context)?)?;
+        }
+        Ok(size)
     }
 }
 
-impl<C: Send + Sync, T> PacketComponent<C> for Vec<T>
+/// A delegate struct which encodes and decodes any collection `Coll` of `T` in the same format
+/// as a `Vec`, a VarInt length followed by the elements.
+///
+/// Decoded elements are collected into `Coll` through `FromIterator`, so the same delegate
+/// targets a `Vec`, `VecDeque`, `HashSet` or a custom container. Encoding iterates the
+/// collection by reference.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::collections::VecDeque;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// cursor.encode_component::<Vec<VarInt>>(&vec![1, 2, 3]).await?;
+/// cursor.set_position(0);
+///
+/// let back = cursor.decode_component::<Collection<VecDeque<i32>, VarInt>>().await?;
+/// assert_eq!(back, VecDeque::from([1, 2, 3]));
+/// # Ok(())
+/// # }
+/// ```
+pub struct Collection<Coll, T>(PhantomData<(Coll, T)>);
+
+impl<C: Send + Sync, Coll, T> PacketComponent<C> for Collection<Coll, T>
 where
     T: PacketComponent<C>,
+    Coll: FromIterator<T::ComponentType> + Send + Sync,
+    for<'a> &'a Coll: IntoIterator<Item = &'a T::ComponentType>,
 {
-    type ComponentType = Vec<T::ComponentType>;
+    type ComponentType = Coll;
 
     decode!(read, context {
         let len = read.read_var_int().await?;
-        let mut vec = Vec::with_capacity(len as usize);
+        if len < 0 {
+            return TransportError::limit_exceeded(0, len, "decoding collection length");
+        }
+        let mut elements = Vec::with_capacity((len as usize).min(UNTRUSTED_CAPACITY));
         for _ in 0..len {
-            vec.push(T::decode(context, read).await?);
+            elements.push(T::decode(context, read).await?);
         }
-        Ok(vec)
+        Ok(elements.into_iter().collect())
     });
 
     encode!(component_ref, write, context {
-        write.write_var_int(component_ref.len() as i32).await?;
+        write.write_var_int(component_ref.into_iter().count() as i32).await?;
         for item in component_ref {
             T::encode(item, context, write).await?;
         }
     });
 
     fn size(component_ref: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
-        let var_int_size = size_var_int(component_ref.len() as i32);
-        let mut dynamic_counter = var_int_size;
-        for item in component_ref {
-            match T::size(item, context)? {
-                Size::Constant(x) => {
-                    return Ok(Size::Dynamic((x * component_ref.len()) + var_int_size));
-                }
-                Size::Dynamic(x) => dynamic_counter += x,
-            }
-        }
-        Ok(Size::Dynamic(dynamic_counter))
+        size_counted::<C, T>(component_ref.into_iter().count(), component_ref, context)
     }
 }
 
@@ -211,12 +836,51 @@ where
 
 #[cfg(test)]
 mod test {
+    use crate::delegate::guard::UNTRUSTED_CAPACITY;
     use crate::prelude::{
-        ByteDrain, DraxReadExt, DraxWriteExt, LimitedVec, SliceU8, VarInt, VecU8,
+        ArcSlice, ByteDrain, ByteLengthPrefixedVec, Collection, DraxReadExt, DraxResult,
+        DraxWriteExt, FixedVec, LimitedByteDrain, LimitedVec, LimitedVecU8, PacketComponent,
+        ParallelArrays2, RleBytes, Size, SliceU8, Terminated, TransportError, VarInt, VecU8,
     };
+    use std::assert_matches::assert_matches;
+    use std::collections::HashSet;
     use std::io::Cursor;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
     use tokio_test::assert_err;
 
+    struct Oversized;
+
+    impl<C: Send + Sync> PacketComponent<C> for Oversized {
+        type ComponentType = ();
+
+        decode!(_read Ok(()));
+
+        encode!(_component_ref, _write);
+
+        fn size(_: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+            Ok(Size::Dynamic(usize::MAX / 2 + 1))
+        }
+    }
+
+    #[test]
+    pub fn test_vec_size_overflow() -> DraxResult<()> {
+        assert_matches!(
+            Vec::<Oversized>::size(&vec![()], &mut ()),
+            Ok(Size::Dynamic(_))
+        );
+        assert_matches!(
+            Vec::<Oversized>::size(&vec![(), ()], &mut ()),
+            Err(TransportError::SizeOverflow)
+        );
+        assert_matches!(
+            FixedVec::<Oversized, 2>::size(&vec![(), ()], &mut ()),
+            Err(TransportError::SizeOverflow)
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     pub async fn byte_drain_sanity() -> crate::prelude::DraxResult<()> {
         let bytes = vec![10, 20, 30];
@@ -244,6 +908,21 @@ mod test {
         Ok(())
     }
 
+    #[cfg(not(feature = "slices"))]
+    #[tokio::test]
+    pub async fn slice_u8_without_slices_feature() -> crate::prelude::DraxResult<()> {
+        let key: [u8; 16] = std::array::from_fn(|i| i as u8);
+
+        let mut cursor: Cursor<Vec<u8>> = Cursor::new(vec![]);
+        cursor.encode_component::<SliceU8<16>>(&key).await?;
+        assert_eq!(cursor.get_ref().as_slice(), key.as_slice());
+        cursor.set_position(0);
+
+        assert_eq!(cursor.decode_component::<SliceU8<16>>().await?, key);
+        Ok(())
+    }
+
+    #[cfg(feature = "slices")]
     #[tokio::test]
     pub async fn slice_sanity() -> crate::prelude::DraxResult<()> {
         type UsedSliceType = [VarInt; 3];
@@ -285,6 +964,40 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    pub async fn fixed_vec_sanity() -> crate::prelude::DraxResult<()> {
+        let values = vec![10, 20, 30];
+
+        let mut cursor: Cursor<Vec<u8>> = Cursor::new(vec![]);
+
+        cursor
+            .encode_component::<FixedVec<VarInt, 3>>(&values)
+            .await?;
+        assert_eq!(cursor.get_ref(), &vec![10, 20, 30]);
+        cursor.set_position(0);
+
+        assert_eq!(
+            cursor.decode_component::<FixedVec<VarInt, 3>>().await?,
+            values
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_fixed_vec_wrong_length() -> crate::prelude::DraxResult<()> {
+        let mut cursor: Cursor<Vec<u8>> = Cursor::new(vec![]);
+
+        let error = cursor
+            .encode_component::<FixedVec<VarInt, 3>>(&vec![10, 20])
+            .await;
+
+        assert_matches!(
+            error,
+            Err(TransportError::LimitExceeded(3, 2, "encoding fixed vec"))
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     pub async fn test_limited_vec_failure() -> crate::prelude::DraxResult<()> {
         let bytes = vec![10, 20, 30];
@@ -299,4 +1012,514 @@ mod test {
         assert_err!(cursor.decode_component::<LimitedVec<VarInt, 2>>().await);
         Ok(())
     }
+
+    #[tokio::test]
+    pub async fn test_limited_vec_u8_negative_length() -> crate::prelude::DraxResult<()> {
+        let mut cursor: Cursor<Vec<u8>> = Cursor::new(vec![]);
+        cursor.write_var_int(-1).await?;
+        cursor.set_position(0);
+
+        let error = cursor.decode_component::<LimitedVecU8<10>>().await;
+
+        assert_matches!(
+            error,
+            Err(TransportError::LimitExceeded(0, -1, "decoding byte vec"))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_limited_vec_u8_failure() -> crate::prelude::DraxResult<()> {
+        let mut cursor: Cursor<Vec<u8>> = Cursor::new(vec![]);
+        cursor.write_var_int(11).await?;
+        cursor.set_position(0);
+
+        let error = cursor.decode_component::<LimitedVecU8<10>>().await;
+
+        assert_matches!(
+            error,
+            Err(TransportError::LimitExceeded(10, 11, "decoding byte vec"))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_parallel_arrays() -> DraxResult<()> {
+        let arrays = (
+            vec![1, 300, 55324],
+            vec!["x".to_string(), "y".to_string(), "z".to_string()],
+        );
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<ParallelArrays2<VarInt, String>>(&arrays)
+            .await?;
+        assert_eq!(
+            cursor.get_ref(),
+            &vec![3, 1, 172, 2, 156, 176, 3, 1, b'x', 1, b'y', 1, b'z']
+        );
+        assert_eq!(
+            ParallelArrays2::<VarInt, String>::size(&arrays, &mut ())?,
+            Size::Dynamic(13)
+        );
+        cursor.set_position(0);
+
+        let back = cursor
+            .decode_component::<ParallelArrays2<VarInt, String>>()
+            .await?;
+        assert_eq!(back, arrays);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_parallel_arrays_length_mismatch() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        let error = cursor
+            .encode_component::<ParallelArrays2<VarInt, VarInt>>(&(vec![1, 2], vec![1]))
+            .await;
+
+        assert_matches!(
+            error,
+            Err(TransportError::LimitExceeded(
+                2,
+                1,
+                "encoding parallel arrays"
+            ))
+        );
+        assert!(cursor.get_ref().is_empty());
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(i32::MAX).await?;
+        cursor.set_position(0);
+        let error = cursor
+            .decode_component::<ParallelArrays2<VarInt, VarInt>>()
+            .await;
+        assert!(error.is_err_and(|e| e.is_eof()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_byte_length_prefixed_vec() -> DraxResult<()> {
+        let values = vec!["first".to_string(), "second".to_string()];
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<ByteLengthPrefixedVec<String>>(&values)
+            .await?;
+        cursor.write_var_int(300).await?;
+        assert_eq!(cursor.get_ref()[0], 13);
+        assert_eq!(
+            ByteLengthPrefixedVec::<String>::size(&values, &mut ())?,
+            Size::Dynamic(14)
+        );
+        cursor.set_position(0);
+
+        let back = cursor
+            .decode_component::<ByteLengthPrefixedVec<String>>()
+            .await?;
+        assert_eq!(back, values);
+        assert_eq!(cursor.read_var_int().await?, 300);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_byte_length_prefixed_vec_partial_element() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![3, 0, 1, 0, 2]);
+
+        let error = cursor
+            .decode_component::<ByteLengthPrefixedVec<u16>>()
+            .await;
+
+        assert!(error.is_err_and(|error| error.is_eof()));
+        Ok(())
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[tokio::test]
+    pub async fn test_small_vec() -> DraxResult<()> {
+        type Small = smallvec::SmallVec<[VarInt; 4]>;
+
+        let inline: <Small as PacketComponent<()>>::ComponentType = smallvec::smallvec![1, 2, 300];
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<Small>(&inline).await?;
+        let mut expected = Cursor::new(vec![]);
+        expected
+            .encode_component::<Vec<VarInt>>(&inline.to_vec())
+            .await?;
+        assert_eq!(cursor.get_ref(), expected.get_ref());
+        assert_eq!(Small::size(&inline, &mut ())?, Size::Dynamic(5));
+
+        cursor.set_position(0);
+        let back = cursor.decode_component::<Small>().await?;
+        assert!(!back.spilled());
+        assert_eq!(back, inline);
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(-1).await?;
+        cursor.set_position(0);
+        let error = cursor.decode_component::<Small>().await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(0, -1, _)));
+        Ok(())
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[tokio::test]
+    pub async fn test_small_vec_spilled() -> DraxResult<()> {
+        type Small = smallvec::SmallVec<[String; 2]>;
+
+        let spilled: <Small as PacketComponent<()>>::ComponentType =
+            (0..5).map(|i| i.to_string()).collect();
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<Small>(&spilled).await?;
+        cursor.set_position(0);
+
+        let back = cursor.decode_component::<Small>().await?;
+        assert!(back.spilled());
+        assert_eq!(back, spilled);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_terminated() -> DraxResult<()> {
+        let values = vec![1, 300, -1];
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<Terminated<VarInt, 0>>(&values)
+            .await?;
+        cursor.write_var_int(7).await?;
+        assert_eq!(
+            Terminated::<VarInt, 0>::size(&values, &mut ())?,
+            Size::Dynamic(cursor.get_ref().len() - 1)
+        );
+        cursor.set_position(0);
+
+        assert_eq!(
+            cursor.decode_component::<Terminated<VarInt, 0>>().await?,
+            values
+        );
+        assert_eq!(cursor.read_var_int().await?, 7);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_terminated_collision() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        let error = cursor
+            .encode_component::<Terminated<VarInt, 0>>(&vec![5, 0])
+            .await;
+
+        assert_matches!(error, Err(TransportError::TerminatorCollision(0)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_arc_slice() -> DraxResult<()> {
+        let values = vec![
+            "shared".to_string(),
+            "across".to_string(),
+            "tasks".to_string(),
+        ];
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<Vec<String>>(&values).await?;
+        cursor.set_position(0);
+
+        let shared = cursor.decode_component::<ArcSlice<String>>().await?;
+        assert_eq!(&*shared, values.as_slice());
+
+        let clone = shared.clone();
+        assert!(Arc::ptr_eq(&shared, &clone));
+        assert_eq!(Arc::strong_count(&shared), 2);
+
+        let mut encoded = Cursor::new(vec![]);
+        encoded.encode_component::<ArcSlice<String>>(&clone).await?;
+        assert_eq!(encoded.get_ref(), cursor.get_ref());
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_arc_slice_truncated() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(3).await?;
+        cursor
+            .encode_component::<String>(&"only one".to_string())
+            .await?;
+        cursor.set_position(0);
+
+        let error = cursor.decode_component::<ArcSlice<String>>().await;
+        assert!(error.is_err_and(|error| error.is_eof()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_arc_slice_untrusted_length() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(i32::MAX).await?;
+        cursor.set_position(0);
+        let error = cursor.decode_component::<ArcSlice<String>>().await;
+        assert!(error.is_err_and(|error| error.is_eof()));
+
+        let values: Vec<i32> = (0..UNTRUSTED_CAPACITY as i32 * 2).collect();
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<Vec<VarInt>>(&values).await?;
+        cursor.set_position(0);
+        let shared = cursor.decode_component::<ArcSlice<VarInt>>().await?;
+        assert_eq!(&*shared, values.as_slice());
+        Ok(())
+    }
+
+    /// Encodes as a VarInt but only reports the size of single byte values as constant.
+    struct ShortVarInt;
+
+    impl<C: Send + Sync> PacketComponent<C> for ShortVarInt {
+        type ComponentType = i32;
+
+        async fn decode<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+            context: &mut C,
+            read: &mut A,
+        ) -> DraxResult<i32> {
+            VarInt::decode(context, read).await
+        }
+
+        async fn encode<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+            component_ref: &i32,
+            context: &mut C,
+            write: &mut A,
+        ) -> DraxResult<()> {
+            VarInt::encode(component_ref, context, write).await
+        }
+
+        fn size(input: &i32, context: &mut C) -> DraxResult<Size> {
+            Ok(match VarInt::size(input, context)? {
+                Size::Dynamic(1) => Size::Constant(1),
+                size => size,
+            })
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_size_sums_varying_elements() -> DraxResult<()> {
+        let values = vec![1, 300, 70000];
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<Vec<ShortVarInt>>(&values).await?;
+        assert_eq!(cursor.get_ref().len(), 7);
+        assert_eq!(
+            Vec::<ShortVarInt>::size(&values, &mut ())?,
+            Size::Dynamic(7)
+        );
+        assert_eq!(Vec::<VarInt>::size(&values, &mut ())?, Size::Dynamic(7));
+        assert_eq!(Vec::<i32>::size(&values, &mut ())?, Size::Dynamic(13));
+        Ok(())
+    }
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    struct DropCounted;
+
+    #[derive(Debug)]
+    struct Counted;
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    impl<C: Send + Sync> PacketComponent<C> for DropCounted {
+        type ComponentType = Counted;
+
+        decode!(read {
+            match read.read_u8().await? {
+                0 => Ok(Counted),
+                byte => TransportError::reserved_byte(byte),
+            }
+        });
+
+        encode!(_component_ref, write {
+            write.write_u8(0).await?;
+        });
+
+        fn size(_: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+            Ok(Size::Constant(1))
+        }
+    }
+
+    #[cfg(feature = "slices")]
+    #[tokio::test]
+    pub async fn test_array_drops_decoded_prefix_on_error() -> DraxResult<()> {
+        let mut bytes: &[u8] = &[0, 7, 0];
+        let error = bytes.decode_component::<[DropCounted; 3]>().await;
+        assert_matches!(error, Err(TransportError::ReservedByte(7)));
+        assert_eq!(DROPPED.load(Ordering::Relaxed), 1);
+
+        let mut bytes: &[u8] = &[0, 0, 0];
+        let array = bytes.decode_component::<[DropCounted; 3]>().await?;
+        assert_eq!(DROPPED.load(Ordering::Relaxed), 1);
+        drop(array);
+        assert_eq!(DROPPED.load(Ordering::Relaxed), 4);
+        Ok(())
+    }
+
+    #[cfg(feature = "slices")]
+    #[tokio::test]
+    pub async fn test_array_size_sums_varying_elements() -> DraxResult<()> {
+        let values = [1, 300, 70000];
+        assert_eq!(
+            <[ShortVarInt; 3]>::size(&values, &mut ())?,
+            Size::Dynamic(6)
+        );
+        assert_eq!(
+            <[ShortVarInt; 2]>::size(&[1, 2], &mut ())?,
+            Size::Constant(2)
+        );
+        assert_eq!(<[i32; 3]>::size(&values, &mut ())?, Size::Constant(12));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_rle_bytes_repetitive() -> DraxResult<()> {
+        let mut bytes = vec![0; 4096];
+        bytes[100..200].fill(7);
+        bytes.push(1);
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<RleBytes<8192>>(&bytes).await?;
+        assert_eq!(cursor.get_ref().len(), 10);
+        assert_eq!(RleBytes::<8192>::size(&bytes, &mut ())?, Size::Dynamic(10));
+
+        cursor.set_position(0);
+        assert_eq!(cursor.decode_component::<RleBytes<8192>>().await?, bytes);
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<RleBytes<8>>(&vec![]).await?;
+        assert_eq!(cursor.get_ref(), &vec![0]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_rle_bytes_expansion_limit() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(2).await?;
+        cursor.write_var_int(1000).await?;
+        cursor.write_u8(1).await?;
+        cursor.write_var_int(i32::MAX).await?;
+        cursor.write_u8(2).await?;
+        assert_eq!(cursor.get_ref().len(), 10);
+
+        cursor.set_position(0);
+        let error = cursor.decode_component::<RleBytes<4096>>().await;
+        assert_matches!(
+            error,
+            Err(TransportError::LimitExceeded(
+                4096,
+                i32::MAX,
+                "decoding run-length bytes"
+            ))
+        );
+
+        let error = Cursor::new(vec![])
+            .encode_component::<RleBytes<4>>(&vec![0; 5])
+            .await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(4, 5, _)));
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<RleBytes<{ usize::MAX }>>(&vec![7; 3])
+            .await?;
+        cursor.set_position(0);
+        assert_eq!(
+            cursor
+                .decode_component::<RleBytes<{ usize::MAX }>>()
+                .await?,
+            vec![7; 3]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_collection_into_vec() -> DraxResult<()> {
+        let values = vec![1, 300, 70000];
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<Collection<Vec<i32>, VarInt>>(&values)
+            .await?;
+        let mut expected = Cursor::new(vec![]);
+        expected.encode_component::<Vec<VarInt>>(&values).await?;
+        assert_eq!(cursor.get_ref(), expected.get_ref());
+        assert_eq!(
+            Collection::<Vec<i32>, VarInt>::size(&values, &mut ())?,
+            Size::Dynamic(7)
+        );
+
+        cursor.set_position(0);
+        let back = cursor
+            .decode_component::<Collection<Vec<i32>, VarInt>>()
+            .await?;
+        assert_eq!(back, values);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_collection_untrusted_length() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(i32::MAX).await?;
+        cursor.set_position(0);
+        let error = cursor
+            .decode_component::<Collection<Vec<String>, String>>()
+            .await;
+        assert!(error.is_err_and(|e| e.is_eof()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_collection_into_hash_set() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<Vec<String>>(&vec![
+                "first".to_string(),
+                "second".to_string(),
+                "first".to_string(),
+            ])
+            .await?;
+        cursor.set_position(0);
+
+        let back = cursor
+            .decode_component::<Collection<HashSet<String>, String>>()
+            .await?;
+        assert_eq!(
+            back,
+            HashSet::from(["first".to_string(), "second".to_string()])
+        );
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<Collection<HashSet<String>, String>>(&back)
+            .await?;
+        assert_eq!(
+            Collection::<HashSet<String>, String>::size(&back, &mut ())?,
+            Size::Dynamic(cursor.get_ref().len())
+        );
+        cursor.set_position(0);
+        assert_eq!(
+            cursor
+                .decode_component::<Collection<HashSet<String>, String>>()
+                .await?,
+            back
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_limited_byte_drain() -> DraxResult<()> {
+        let mut bytes = [7u8; 100].as_slice();
+        let back = bytes.decode_component::<LimitedByteDrain<100>>().await?;
+        assert_eq!(back, vec![7; 100]);
+
+        let mut bytes = [7u8; 101].as_slice();
+        let error = bytes.decode_component::<LimitedByteDrain<100>>().await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(100, 101, _)));
+
+        let error = vec![]
+            .encode_component::<LimitedByteDrain<100>>(&vec![7; 101])
+            .await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(100, 101, _)));
+        Ok(())
+    }
 }
@@ -0,0 +1,111 @@
+use crate::prelude::{DraxResult, PacketComponent, Size};
+use std::marker::PhantomData;
+
+/// Defines a context which carries the protocol version of the connection being decoded or
+/// encoded.
+pub trait HasProtocolVersion {
+    fn protocol_version(&self) -> i32;
+}
+
+impl HasProtocolVersion for i32 {
+    fn protocol_version(&self) -> i32 {
+        *self
+    }
+}
+
+/// A delegate struct which encodes and decodes a `T` only while the protocol version of the
+/// context is within `SINCE..UNTIL`.
+///
+/// Outside of the range the field is absent from the wire, nothing is written and decoding
+/// produces the default value without reading.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use drax::delegate::version::Between;
+/// # async fn test() -> DraxResult<()> {
+/// let mut bytes = vec![];
+/// Between::<47, 107, VarInt>::encode(&300, &mut 340, &mut bytes).await?;
+/// assert!(bytes.is_empty());
+///
+/// Between::<47, 107, VarInt>::encode(&300, &mut 47, &mut bytes).await?;
+/// assert_eq!(bytes, vec![172, 2]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Between<const SINCE: i32, const UNTIL: i32, T>(PhantomData<T>);
+
+impl<const SINCE: i32, const UNTIL: i32, T> Between<SINCE, UNTIL, T> {
+    fn present<C: HasProtocolVersion>(context: &C) -> bool {
+        (SINCE..UNTIL).contains(&context.protocol_version())
+    }
+}
+
+impl<C: HasProtocolVersion + Send + Sync, const SINCE: i32, const UNTIL: i32, T> PacketComponent<C>
+    for Between<SINCE, UNTIL, T>
+where
+    T: PacketComponent<C>,
+    T::ComponentType: Default,
+{
+    type ComponentType = T::ComponentType;
+
+    decode!(read, context {
+        if Self::present(context) {
+            T::decode(context, read).await
+        } else {
+            Ok(T::ComponentType::default())
+        }
+    });
+
+    encode!(component_ref, write, context {
+        if Self::present(context) {
+            T::encode(component_ref, context, write).await?;
+        }
+    });
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        if Self::present(context) {
+            Ok(Size::Dynamic(T::size(input, context)?.bytes()))
+        } else {
+            Ok(Size::Dynamic(0))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::delegate::version::Between;
+    use crate::prelude::{DraxResult, PacketComponent, Size, VarInt};
+
+    type Ranged = Between<47, 107, VarInt>;
+
+    async fn round_trip(version: i32, value: i32) -> DraxResult<(Vec<u8>, i32)> {
+        let mut version = version;
+        let mut bytes = vec![];
+        Ranged::encode(&value, &mut version, &mut bytes).await?;
+        assert_eq!(
+            Ranged::size(&value, &mut version)?,
+            Size::Dynamic(bytes.len())
+        );
+        let back = Ranged::decode(&mut version, &mut bytes.as_slice()).await?;
+        Ok((bytes, back))
+    }
+
+    #[tokio::test]
+    pub async fn test_between_below_range() -> DraxResult<()> {
+        assert_eq!(round_trip(46, 300).await?, (vec![], 0));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_between_in_range() -> DraxResult<()> {
+        assert_eq!(round_trip(47, 300).await?, (vec![172, 2], 300));
+        assert_eq!(round_trip(106, 300).await?, (vec![172, 2], 300));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_between_above_range() -> DraxResult<()> {
+        assert_eq!(round_trip(107, 300).await?, (vec![], 0));
+        Ok(())
+    }
+}
@@ -17,9 +17,68 @@ pub enum TransportError {
     /// An error occurred while trying to decode a UTF-8 string.
     #[error(transparent)]
     Utf8Error(#[from] std::string::FromUtf8Error),
+    /// An error occurred while trying to validate borrowed bytes as UTF-8.
+    #[error(transparent)]
+    InvalidUtf8(#[from] std::str::Utf8Error),
     /// A limit exceeded during decoding or encoding.
     #[error("Limit exceeded while {2}. Expected {0} but received {1}.")]
     LimitExceeded(i32, i32, &'static str),
+    /// The size declared for a component did not match the number of bytes it encoded.
+    #[error("Size mismatch while encoding. Declared {0} bytes but encoded {1}.")]
+    SizeMismatch(usize, usize),
+    /// A packet was received with a different id than the one expected.
+    #[error("Unexpected packet id. Expected {0} but received {1}.")]
+    UnexpectedPacketId(i32, i32),
+    /// A discriminant was read which does not correspond to any known variant.
+    #[error("Invalid variant discriminant {0}.")]
+    InvalidVariant(i32),
+    /// A component did not consume all the bytes of the frame it was decoded from.
+    #[error("Frame decoded with {0} trailing bytes remaining.")]
+    TrailingBytes(u64),
+    /// The checksum of a frame did not match the checksum computed over its body.
+    #[error("Checksum mismatch. Expected {0:#010x} but computed {1:#010x}.")]
+    ChecksumMismatch(u32, u32),
+    /// A dynamic value did not hold the type registered for its id.
+    #[error("Dynamic value for id {0} does not match the registered component type.")]
+    DynamicTypeMismatch(i32),
+    /// A value was encoded which is equal to the sentinel reserved for representing none.
+    #[error("Value {0} collides with the sentinel used to encode none.")]
+    SentinelCollision(i32),
+    /// A value was encoded whose bytes would be read as the terminator of its collection or
+    /// delimited string.
+    #[error("Encoding collides with the terminator {0:#04x}.")]
+    TerminatorCollision(u8),
+    /// The size of a component overflowed while it was being calculated.
+    #[error("Size overflowed while calculating the size of a component.")]
+    SizeOverflow,
+    /// A reserved byte was decoded which is not zero.
+    #[error("Reserved byte must be zero but was {0:#04x}.")]
+    ReservedByte(u8),
+    /// An index was decoded or encoded which does not refer to an entry of its table.
+    #[error("Index {index} out of bounds for a table of length {len}.")]
+    IndexOutOfBounds { index: i32, len: i32 },
+    /// Components were nested deeper than the depth limit of the context.
+    #[error("Nesting depth exceeded the limit of {0}.")]
+    DepthExceeded(u32),
+    /// The frame ended before every element claimed by a collection was decoded. The flag is
+    /// set if the frame ended partway through an element rather than between two elements.
+    #[error(
+        "Frame ended before the claimed element count was decoded (mid-element: {expected_more})."
+    )]
+    FrameUnderflow { expected_more: bool },
+    /// A range was decoded or encoded whose start is greater than its end.
+    #[error("Range start is greater than its end.")]
+    InvalidRange,
+    /// A value was rejected by the predicate validating it.
+    #[error("Value rejected: {0}.")]
+    Rejected(String),
+    /// An error raised while decoding a named field, the path lists the enclosing fields from
+    /// the outermost to the one which failed.
+    #[error("{} `{path}`: {source}", field_error_kind(.source))]
+    Field {
+        path: String,
+        source: Box<TransportError>,
+    },
     /// An error occurred during the serialization or deserialization process from serde_json.
     #[cfg(feature = "serde")]
     #[error(transparent)]
@@ -34,6 +93,104 @@ impl TransportError {
     pub fn limit_exceeded<T>(expected: i32, received: i32, context: &'static str) -> DraxResult<T> {
         Err(Self::LimitExceeded(expected, received, context))
     }
+
+    pub fn size_mismatch<T>(declared: usize, encoded: usize) -> DraxResult<T> {
+        Err(Self::SizeMismatch(declared, encoded))
+    }
+
+    pub fn unexpected_packet_id<T>(expected: i32, received: i32) -> DraxResult<T> {
+        Err(Self::UnexpectedPacketId(expected, received))
+    }
+
+    pub fn invalid_variant<T>(discriminant: i32) -> DraxResult<T> {
+        Err(Self::InvalidVariant(discriminant))
+    }
+
+    pub fn trailing_bytes<T>(remaining: u64) -> DraxResult<T> {
+        Err(Self::TrailingBytes(remaining))
+    }
+
+    pub fn sentinel_collision<T>(sentinel: i32) -> DraxResult<T> {
+        Err(Self::SentinelCollision(sentinel))
+    }
+
+    pub fn terminator_collision<T>(terminator: u8) -> DraxResult<T> {
+        Err(Self::TerminatorCollision(terminator))
+    }
+
+    pub fn size_overflow<T>() -> DraxResult<T> {
+        Err(Self::SizeOverflow)
+    }
+
+    pub fn reserved_byte<T>(byte: u8) -> DraxResult<T> {
+        Err(Self::ReservedByte(byte))
+    }
+
+    pub fn index_out_of_bounds<T>(index: i32, len: i32) -> DraxResult<T> {
+        Err(Self::IndexOutOfBounds { index, len })
+    }
+
+    pub fn depth_exceeded<T>(limit: u32) -> DraxResult<T> {
+        Err(Self::DepthExceeded(limit))
+    }
+
+    pub fn frame_underflow<T>(expected_more: bool) -> DraxResult<T> {
+        Err(Self::FrameUnderflow { expected_more })
+    }
+
+    pub fn invalid_range<T>() -> DraxResult<T> {
+        Err(Self::InvalidRange)
+    }
+
+    pub fn rejected<T>(reason: impl Into<String>) -> DraxResult<T> {
+        Err(Self::Rejected(reason.into()))
+    }
+
+    /// Attaches the name of the field being decoded to an error, I/O errors included.
+    ///
+    /// Errors which already carry a field path are prefixed with the name, so wrapping the
+    /// error at every level of a nested decode produces the full path to the failing field.
+    ///
+    /// ```rust
+    /// # use drax::prelude::*;
+    /// let error = TransportError::from(std::io::Error::from(std::io::ErrorKind::BrokenPipe));
+    /// let error = TransportError::in_field("name", error);
+    /// let error = TransportError::in_field("profile", error);
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "I/O error decoding field `profile.name`: broken pipe"
+    /// );
+    /// ```
+    pub fn in_field(name: &str, error: TransportError) -> TransportError {
+        match error {
+            Self::Field { path, source } => Self::Field {
+                path: format!("{name}.{path}"),
+                source,
+            },
+            error => Self::Field {
+                path: name.to_string(),
+                source: Box::new(error),
+            },
+        }
+    }
+
+    /// Returns true if the error was caused by the stream ending, either reported by drax itself
+    /// or by the underlying reader as an unexpected EOF.
+    pub fn is_eof(&self) -> bool {
+        match self {
+            Self::EOF => true,
+            Self::IoError(error) => error.kind() == std::io::ErrorKind::UnexpectedEof,
+            Self::Field { source, .. } => source.is_eof(),
+            _ => false,
+        }
+    }
+}
+
+fn field_error_kind(source: &TransportError) -> &'static str {
+    match source {
+        TransportError::IoError(_) => "I/O error decoding field",
+        _ => "Error decoding field",
+    }
 }
 
 /// Result type alias for transport errors.
@@ -53,6 +210,12 @@ pub enum NbtError {
     AccounterOverflow,
     #[error("Cesu8 decoding error. {0}")]
     Cesu8DecodingError(#[from] cesu8::Cesu8DecodingError),
+    #[error("Nbt error at `{path}` (offset {offset}). {source}")]
+    At {
+        path: String,
+        offset: u64,
+        source: Box<NbtError>,
+    },
 }
 
 #[cfg(feature = "nbt")]
@@ -72,4 +235,88 @@ impl NbtError {
     pub fn accounter_overflow<T>() -> DraxResult<T> {
         Err(Self::AccounterOverflow.into())
     }
+
+    /// Attaches a path segment and the stream offset to an nbt error raised while reading a
+    /// nested tag. Errors which already carry a path are prefixed with the segment, keeping the
+    /// offset where the error was first detected. Non-nbt errors are returned unchanged.
+    pub fn at(segment: &str, offset: u64, error: TransportError) -> TransportError {
+        match error {
+            TransportError::NbtError(NbtError::At {
+                path,
+                offset,
+                source,
+            }) => NbtError::At {
+                path: format!("{segment} -> {path}"),
+                offset,
+                source,
+            }
+            .into(),
+            TransportError::NbtError(source) => NbtError::At {
+                path: segment.to_string(),
+                offset,
+                source: Box::new(source),
+            }
+            .into(),
+            error => error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{DraxResult, PacketComponent, TransportError, VarInt};
+    use std::assert_matches::assert_matches;
+    use std::io::Cursor;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::io::{AsyncRead, AsyncReadExt, ReadBuf};
+
+    struct ResetReader;
+
+    impl AsyncRead for ResetReader {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            _: &mut ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Err(std::io::ErrorKind::ConnectionReset.into()))
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_io_error_carries_field_path() -> DraxResult<()> {
+        let mut read = Cursor::new(vec![10]).chain(ResetReader);
+        let id = VarInt::decode(&mut (), &mut read).await?;
+        assert_eq!(id, 10);
+
+        let error = String::decode(&mut (), &mut read)
+            .await
+            .map_err(|error| TransportError::in_field("name", error))
+            .map_err(|error| TransportError::in_field("profile", error))
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "I/O error decoding field `profile.name`: {}",
+                std::io::Error::from(std::io::ErrorKind::ConnectionReset)
+            )
+        );
+        assert_matches!(
+            error,
+            TransportError::Field { ref path, ref source }
+                if path == "profile.name" && matches!(**source, TransportError::IoError(_))
+        );
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_field_path_eof() {
+        let error = TransportError::in_field("name", TransportError::EOF);
+        assert_eq!(
+            error.to_string(),
+            "Error decoding field `name`: End of file reached."
+        );
+        assert!(error.is_eof());
+    }
 }
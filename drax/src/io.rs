@@ -0,0 +1,925 @@
+#[cfg(feature = "compression")]
+use crate::delegate::compression::{compress_bound, deflate, inflate};
+use crate::prelude::DraxResult;
+#[cfg(any(feature = "bytes", feature = "compression"))]
+use crate::prelude::TransportError;
+#[cfg(feature = "compression")]
+use crate::prelude::{measured_encode, size_var_int, DraxReadExt, DraxWriteExt, PacketComponent};
+#[cfg(feature = "compression")]
+use crate::transport::{return_scratch, take_scratch};
+#[cfg(feature = "bytes")]
+use bytes::{Buf, Bytes, BytesMut};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+#[cfg(feature = "bytes")]
+use tokio::io::AsyncReadExt;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, ReadBuf};
+
+/// A reader adapter which counts the number of bytes read through it.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use drax::io::CountingReader;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut reader = CountingReader::new(Cursor::new(vec![172, 2, 10]));
+/// let value = reader.decode_component::<VarInt>().await?;
+/// assert_eq!(value, 300);
+/// assert_eq!(reader.count(), 2);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct CountingReader<R> {
+    inner: R,
+    count: u64,
+}
+
+impl<R> CountingReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner, count: 0 }
+    }
+
+    /// Returns the number of bytes read through this reader so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for CountingReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let before = buf.filled().len();
+        ready!(Pin::new(&mut self.inner).poll_read(cx, buf))?;
+        self.count += (buf.filled().len() - before) as u64;
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// A reader adapter which copies every byte read through it into a buffer.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use drax::io::TeeReader;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut reader = TeeReader::new(Cursor::new(vec![172, 2, 10]));
+/// assert_eq!(reader.read_var_int().await?, 300);
+/// assert_eq!(reader.captured(), &[172, 2]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TeeReader<R> {
+    inner: R,
+    captured: Vec<u8>,
+}
+
+impl<R> TeeReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            captured: Vec::new(),
+        }
+    }
+
+    /// Returns the bytes read through this reader so far.
+    pub fn captured(&self) -> &[u8] {
+        &self.captured
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader along with the bytes read through this reader.
+    pub fn into_parts(self) -> (R, Vec<u8>) {
+        (self.inner, self.captured)
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for TeeReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let before = buf.filled().len();
+        ready!(Pin::new(&mut self.inner).poll_read(cx, buf))?;
+        self.captured.extend_from_slice(&buf.filled()[before..]);
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Tracks the number of bytes remaining in the frame currently being decoded.
+///
+/// A codec which knows the length of a frame creates a `FrameRemaining` for it, stores it in
+/// the context and decodes through the reader returned by [`FrameRemaining::track`]. Every byte
+/// read through the tracked reader is deducted from the frame, so components decoded later in
+/// the frame can ask how much of it is left. Clones share the same count.
+///
+/// The default value is an empty frame with no bytes remaining.
+#[derive(Clone, Debug, Default)]
+pub struct FrameRemaining {
+    len: u64,
+    consumed: Arc<AtomicU64>,
+}
+
+impl FrameRemaining {
+    pub fn new(len: u64) -> Self {
+        Self {
+            len,
+            consumed: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Returns the number of bytes of the frame which have not been read yet.
+    pub fn remaining(&self) -> u64 {
+        self.len
+            .saturating_sub(self.consumed.load(Ordering::Relaxed))
+    }
+
+    /// Wraps the reader so every byte read through it is deducted from this frame.
+    pub fn track<R>(&self, inner: R) -> FrameTrackingReader<R> {
+        FrameTrackingReader {
+            inner,
+            consumed: self.consumed.clone(),
+        }
+    }
+}
+
+/// A reader adapter which deducts every byte read through it from a [`FrameRemaining`].
+#[derive(Debug)]
+pub struct FrameTrackingReader<R> {
+    inner: R,
+    consumed: Arc<AtomicU64>,
+}
+
+impl<R> FrameTrackingReader<R> {
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for FrameTrackingReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let before = buf.filled().len();
+        ready!(Pin::new(&mut self.inner).poll_read(cx, buf))?;
+        self.consumed
+            .fetch_add((buf.filled().len() - before) as u64, Ordering::Relaxed);
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// A writer adapter which counts the number of bytes written through it.
+#[derive(Debug)]
+pub struct CountingWriter<W> {
+    inner: W,
+    count: u64,
+}
+
+impl<W> CountingWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, count: 0 }
+    }
+
+    /// Returns the number of bytes written through this writer so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for CountingWriter<W> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let written = ready!(Pin::new(&mut self.inner).poll_write(cx, buf))?;
+        self.count += written as u64;
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+/// A writer adapter which buffers everything written through it until the end of the packet.
+///
+/// Nothing reaches the inner writer until [`DraxBufWriter::end_packet`] is called, at which
+/// point the whole packet is written and the inner writer is flushed. Partial packets are never
+/// visible on the wire and flushing the adapter only flushes the inner writer, this leaves the
+/// batching of packets under the control of the caller.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use drax::io::DraxBufWriter;
+/// # async fn test() -> DraxResult<()> {
+/// let mut writer = DraxBufWriter::new(vec![]);
+/// writer.write_var_int(300).await?;
+/// writer.encode_component::<String>(&"test".to_string()).await?;
+/// assert!(writer.get_ref().is_empty());
+/// writer.end_packet().await?;
+/// assert_eq!(writer.get_ref().len(), 7);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DraxBufWriter<W> {
+    inner: W,
+    buffer: Vec<u8>,
+}
+
+impl<W> DraxBufWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self::with_capacity(inner, 0)
+    }
+
+    pub fn with_capacity(inner: W, capacity: usize) -> Self {
+        Self {
+            inner,
+            buffer: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the bytes of the current packet which have not been written yet.
+    pub fn pending(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the inner writer, any pending bytes are discarded.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite + Unpin> DraxBufWriter<W> {
+    /// Ends the current packet, writing it to the inner writer and flushing the inner writer.
+    pub async fn end_packet(&mut self) -> DraxResult<()> {
+        self.inner.write_all(&self.buffer).await?;
+        self.buffer.clear();
+        self.inner.flush().await?;
+        Ok(())
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for DraxBufWriter<W> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        self.buffer.extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+/// A stream adapter which XORs every byte read or written with a repeating key.
+///
+/// Reads and writes each keep their own offset into the key, carried across calls, so the mask
+/// lines up however the bytes are split. Masking is its own inverse, the same key unmasks what
+/// it masked. This is a lightweight obfuscation layer, not encryption.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use drax::io::MaskedStream;
+/// # async fn test() -> DraxResult<()> {
+/// let mut writer = MaskedStream::new(vec![], vec![0xFF]);
+/// writer.write_var_int(300).await?;
+/// assert_eq!(writer.get_ref(), &vec![!172, !2]);
+///
+/// let masked = writer.into_inner();
+/// let mut reader = MaskedStream::new(masked.as_slice(), vec![0xFF]);
+/// assert_eq!(reader.read_var_int().await?, 300);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct MaskedStream<S> {
+    inner: S,
+    key: Box<[u8]>,
+    read_offset: usize,
+    write_offset: usize,
+    scratch: Vec<u8>,
+}
+
+impl<S> MaskedStream<S> {
+    /// Creates the adapter, panics if the key is empty.
+    pub fn new(inner: S, key: impl Into<Box<[u8]>>) -> Self {
+        let key = key.into();
+        assert!(!key.is_empty(), "mask key must not be empty");
+        Self {
+            inner,
+            key,
+            read_offset: 0,
+            write_offset: 0,
+            scratch: Vec::new(),
+        }
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// XORs the bytes with the key starting at the offset, returning the offset after them.
+    fn mask(key: &[u8], offset: usize, bytes: &mut [u8]) -> usize {
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            *byte ^= key[(offset + index) % key.len()];
+        }
+        (offset + bytes.len()) % key.len()
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for MaskedStream<S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let before = buf.filled().len();
+        ready!(Pin::new(&mut self.inner).poll_read(cx, buf))?;
+        let me = &mut *self;
+        me.read_offset = Self::mask(&me.key, me.read_offset, &mut buf.filled_mut()[before..]);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for MaskedStream<S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let me = &mut *self;
+        me.scratch.clear();
+        me.scratch.extend_from_slice(buf);
+        Self::mask(&me.key, me.write_offset, &mut me.scratch);
+
+        let written = ready!(Pin::new(&mut me.inner).poll_write(cx, &me.scratch))?;
+        me.write_offset = (me.write_offset + written) % me.key.len();
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+/// A reader which accumulates length-prefixed frames, each prefixed by its VarInt length, and
+/// returns every complete frame as owned `Bytes`.
+///
+/// Partially received frames are kept in the accumulator between calls, so
+/// [`FrameReader::read_frame`] is cancel safe and may be raced in a `select!` without losing
+/// data. Frames are decoded by reading from the returned bytes, no further I/O is involved.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use drax::io::FrameReader;
+/// # async fn test() -> DraxResult<()> {
+/// let bytes = vec![2, 172, 2, 1, 10];
+/// let mut reader = FrameReader::new(bytes.as_slice(), 1024);
+///
+/// let frame = reader.read_frame().await?.unwrap();
+/// assert_eq!(frame.as_ref().decode_component::<VarInt>().await?, 300);
+/// assert_eq!(reader.read_frame().await?.as_deref(), Some(&[10][..]));
+/// assert_eq!(reader.read_frame().await?, None);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "bytes")]
+#[derive(Debug)]
+pub struct FrameReader<R> {
+    inner: R,
+    buffer: BytesMut,
+    max_len: usize,
+}
+
+#[cfg(feature = "bytes")]
+impl<R> FrameReader<R> {
+    /// Creates a frame reader rejecting frames declaring more than `max_len` bytes.
+    pub fn new(inner: R, max_len: usize) -> Self {
+        Self {
+            inner,
+            buffer: BytesMut::new(),
+            max_len,
+        }
+    }
+
+    /// Returns the bytes received which do not form a complete frame yet.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Splits the next frame off the accumulator without reading, returning `None` if more
+    /// data is needed to complete it.
+    pub fn try_frame(&mut self) -> DraxResult<Option<Bytes>> {
+        let mut value = 0u32;
+        for (index, byte) in self.buffer.iter().take(5).enumerate() {
+            value |= ((byte & 0x7F) as u32) << (7 * index);
+            if byte & 0x80 != 0 {
+                continue;
+            }
+
+            let len = value as i32;
+            if len < 0 || len as usize > self.max_len {
+                return TransportError::limit_exceeded(
+                    self.max_len.min(i32::MAX as usize) as i32,
+                    len,
+                    "decoding frame length",
+                );
+            }
+            let frame_len = index + 1 + len as usize;
+            if self.buffer.len() < frame_len {
+                self.buffer.reserve(frame_len - self.buffer.len());
+                return Ok(None);
+            }
+            self.buffer.advance(index + 1);
+            return Ok(Some(self.buffer.split_to(len as usize).freeze()));
+        }
+        if self.buffer.len() >= 5 {
+            return Err(TransportError::VarNumTooLarge);
+        }
+        Ok(None)
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader, any buffered bytes are discarded.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<R: AsyncRead + Unpin> FrameReader<R> {
+    /// Reads until a complete frame is buffered and returns it, or `None` if the reader ends
+    /// between frames. A reader ending within a frame fails with `TransportError::EOF`.
+    pub async fn read_frame(&mut self) -> DraxResult<Option<Bytes>> {
+        loop {
+            if let Some(frame) = self.try_frame()? {
+                return Ok(Some(frame));
+            }
+            if self.inner.read_buf(&mut self.buffer).await? == 0 {
+                if self.buffer.is_empty() {
+                    return Ok(None);
+                }
+                return Err(TransportError::EOF);
+            }
+        }
+    }
+}
+
+/// A transport which reads and writes packets in the Minecraft compressed frame format.
+///
+/// Every packet is written as `[VarInt frame length][VarInt data length][body]`, where the frame
+/// length counts the data length and body. Packets whose encoded body is at least `threshold`
+/// bytes are deflated with zlib and the data length holds their uncompressed length, smaller
+/// packets are written as is behind a data length of `0`. Reading rejects compressed packets
+/// which declare a length below `threshold`, and uncompressed lengths above `MAX` are rejected
+/// with `TransportError::LimitExceeded` on both sides before anything is inflated.
+///
+/// Encryption belongs to the stream itself, pass a ciphered or [`MaskedStream`] as `S` so the
+/// frame lengths are covered too.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use drax::io::LayeredTransport;
+/// # async fn test() -> DraxResult<()> {
+/// let mut transport = LayeredTransport::<_, 1024>::new(vec![], 256);
+/// transport.write_packet::<String, _>(&"hello".to_string(), &mut ()).await?;
+///
+/// let bytes = transport.into_inner();
+/// assert_eq!(bytes, [7, 0, 5, b'h', b'e', b'l', b'l', b'o']);
+/// let mut transport = LayeredTransport::<_, 1024>::new(bytes.as_slice(), 256);
+/// assert_eq!(transport.read_packet::<String, _>(&mut ()).await?, "hello");
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "compression")]
+#[derive(Debug)]
+pub struct LayeredTransport<S, const MAX: usize> {
+    stream: S,
+    threshold: usize,
+}
+
+#[cfg(feature = "compression")]
+impl<S, const MAX: usize> LayeredTransport<S, MAX> {
+    pub fn new(stream: S, threshold: usize) -> Self {
+        Self { stream, threshold }
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    const LIMIT: i32 = if MAX > i32::MAX as usize {
+        i32::MAX
+    } else {
+        MAX as i32
+    };
+}
+
+#[cfg(feature = "compression")]
+impl<S: AsyncRead + Unpin + Send + Sync, const MAX: usize> LayeredTransport<S, MAX> {
+    /// Reads the next frame from the stream and decodes the packet it holds, inflating it if
+    /// it was compressed.
+    pub async fn read_packet<P, C>(&mut self, context: &mut C) -> DraxResult<P::ComponentType>
+    where
+        P: PacketComponent<C>,
+        C: Send + Sync,
+    {
+        let frame_len = self.stream.read_var_int().await?;
+        if frame_len < 0 {
+            return TransportError::limit_exceeded(0, frame_len, "decoding packet frame");
+        }
+        let max_frame = size_var_int(Self::LIMIT) + compress_bound(Self::LIMIT as usize);
+        let frame = self
+            .stream
+            .read_exact_limited(frame_len as usize, max_frame)
+            .await?;
+
+        let mut slice = frame.as_slice();
+        let data_len = slice.read_var_int().await?;
+        let inflated;
+        let mut body = if data_len == 0 {
+            if slice.len() > Self::LIMIT as usize {
+                return TransportError::limit_exceeded(
+                    Self::LIMIT,
+                    i32::try_from(slice.len()).unwrap_or(i32::MAX),
+                    "decoding packet frame",
+                );
+            }
+            slice
+        } else {
+            if data_len < 0 || data_len > Self::LIMIT {
+                return TransportError::limit_exceeded(
+                    Self::LIMIT,
+                    data_len,
+                    "decoding packet frame",
+                );
+            }
+            if (data_len as usize) < self.threshold {
+                return TransportError::rejected(format!(
+                    "compressed packet of {data_len} bytes is below the threshold of {}",
+                    self.threshold
+                ));
+            }
+            inflated = inflate(slice, data_len as usize)?;
+            inflated.as_slice()
+        };
+
+        let packet = P::decode(context, &mut body).await?;
+        if !body.is_empty() {
+            return TransportError::trailing_bytes(body.len() as u64);
+        }
+        Ok(packet)
+    }
+}
+
+#[cfg(feature = "compression")]
+impl<S: AsyncWrite + Unpin + Send + Sync, const MAX: usize> LayeredTransport<S, MAX> {
+    /// Encodes the packet, compressing it if it reaches the threshold, writes it to the stream
+    /// in its frame and flushes the stream.
+    pub async fn write_packet<P, C>(
+        &mut self,
+        component: &P::ComponentType,
+        context: &mut C,
+    ) -> DraxResult<()>
+    where
+        P: PacketComponent<C>,
+        C: Send + Sync,
+    {
+        let (body, len) = measured_encode::<P, C>(component, context).await?;
+        if len > MAX {
+            return TransportError::limit_exceeded(
+                Self::LIMIT,
+                i32::try_from(len).unwrap_or(i32::MAX),
+                "encoding packet frame",
+            );
+        }
+
+        let mut frame = take_scratch();
+        let result = async {
+            if len >= self.threshold {
+                frame.write_var_int(len as i32).await?;
+                frame.extend_from_slice(&deflate(&body)?);
+            } else {
+                frame.write_var_int(0).await?;
+                frame.extend_from_slice(&body);
+            }
+            self.stream.write_var_int(frame.len() as i32).await?;
+            self.stream.write_all(&frame).await?;
+            self.stream.flush().await?;
+            DraxResult::Ok(())
+        }
+        .await;
+        return_scratch(frame);
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::io::{
+        CountingReader, CountingWriter, DraxBufWriter, FrameReader, LayeredTransport, MaskedStream,
+    };
+    use crate::prelude::{
+        ByteDrain, DraxReadExt, DraxResult, DraxWriteExt, LengthDelimited, TransportError, VarInt,
+    };
+    use flate2::read::ZlibDecoder;
+    use std::assert_matches::assert_matches;
+    use std::io::{Cursor, Read};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    pub async fn test_counting_reader() -> DraxResult<()> {
+        let mut reader = CountingReader::new(Cursor::new(vec![156, 176, 3, 0, 0, 0, 10]));
+        assert_eq!(reader.read_var_int().await?, 55324);
+        assert_eq!(reader.count(), 3);
+        assert_eq!(reader.decode_component::<i32>().await?, 10);
+        assert_eq!(reader.count(), 7);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_counting_writer() -> DraxResult<()> {
+        let mut writer = CountingWriter::new(Cursor::new(vec![]));
+        writer.write_var_int(55324).await?;
+        assert_eq!(writer.count(), 3);
+        writer.encode_component::<i32>(&10).await?;
+        assert_eq!(writer.count(), 7);
+        assert_eq!(writer.into_inner().into_inner().len(), 7);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_masked_stream_round_trip() -> DraxResult<()> {
+        let key = [0x5A, 0xC3, 0x0F];
+        let plain: Vec<u8> = (0..32).collect();
+
+        let mut writer = MaskedStream::new(vec![], key);
+        for chunk in [&plain[..1], &plain[1..3], &plain[3..8], &plain[8..]] {
+            writer.write_all(chunk).await?;
+        }
+        let masked = writer.into_inner();
+        let expected: Vec<u8> = plain
+            .iter()
+            .enumerate()
+            .map(|(index, byte)| byte ^ key[index % key.len()])
+            .collect();
+        assert_eq!(masked, expected);
+
+        let mut mock = tokio_test::io::Builder::new();
+        for chunk in [&masked[..2], &masked[2..7], &masked[7..8], &masked[8..]] {
+            mock.read(chunk);
+        }
+        let mut reader = MaskedStream::new(mock.build(), key);
+        let mut back = vec![];
+        reader.read_to_end(&mut back).await?;
+        assert_eq!(back, plain);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_buf_writer_flushes_at_packet_end() -> DraxResult<()> {
+        let mut writer = DraxBufWriter::new(vec![]);
+        writer.write_var_int(55324).await?;
+        writer.encode_component::<i32>(&10).await?;
+        writer.flush().await?;
+        assert!(writer.get_ref().is_empty());
+        assert_eq!(writer.pending().len(), 7);
+
+        writer.end_packet().await?;
+        assert_eq!(writer.get_ref(), &vec![156, 176, 3, 0, 0, 0, 10]);
+        assert!(writer.pending().is_empty());
+
+        writer.write_var_int(25).await?;
+        assert_eq!(writer.get_ref().len(), 7);
+        writer.end_packet().await?;
+        assert_eq!(writer.get_ref().len(), 8);
+        Ok(())
+    }
+
+    async fn frames() -> DraxResult<Vec<u8>> {
+        let mut bytes = vec![];
+        bytes
+            .encode_component::<LengthDelimited<VarInt>>(&300)
+            .await?;
+        bytes
+            .encode_component::<LengthDelimited<String>>(&"frame".to_string())
+            .await?;
+        bytes.write_var_int(0).await?;
+        bytes
+            .encode_component::<LengthDelimited<String>>(&"x".repeat(200))
+            .await?;
+        Ok(bytes)
+    }
+
+    #[tokio::test]
+    pub async fn test_frame_reader_chunked() -> DraxResult<()> {
+        let bytes = frames().await?;
+        for chunk in [1, 2, 3, 7, bytes.len()] {
+            let mut builder = tokio_test::io::Builder::new();
+            for part in bytes.chunks(chunk) {
+                builder.read(part);
+            }
+            let mut reader = FrameReader::new(builder.build(), 1024);
+
+            let frame = reader.read_frame().await?.unwrap();
+            assert_eq!(frame.as_ref().decode_component::<VarInt>().await?, 300);
+            let frame = reader.read_frame().await?.unwrap();
+            assert_eq!(frame.as_ref().decode_component::<String>().await?, "frame");
+            assert!(reader.read_frame().await?.unwrap().is_empty());
+            let frame = reader.read_frame().await?.unwrap();
+            assert_eq!(frame.len(), 202);
+            assert_eq!(reader.read_frame().await?, None);
+            assert!(reader.buffered().is_empty());
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_frame_reader_partial_frames() -> DraxResult<()> {
+        let mut reader = FrameReader::new(&[3, 1, 2][..], 1024);
+        assert_matches!(reader.read_frame().await, Err(TransportError::EOF));
+        assert_eq!(reader.buffered(), &[3, 1, 2]);
+
+        let mut reader = FrameReader::new(&[172][..], 1024);
+        assert_eq!(reader.try_frame()?, None);
+        assert_matches!(reader.read_frame().await, Err(TransportError::EOF));
+        assert_eq!(reader.try_frame()?, None);
+
+        let mut reader = FrameReader::new(&[0x80; 5][..], 1024);
+        assert_matches!(
+            reader.read_frame().await,
+            Err(TransportError::VarNumTooLarge)
+        );
+
+        let mut reader = FrameReader::new(&[172, 2][..], 16);
+        assert_matches!(
+            reader.read_frame().await,
+            Err(TransportError::LimitExceeded(16, 300, _))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_layered_transport() -> DraxResult<()> {
+        const KEY: [u8; 4] = [0x13, 0x37, 0xC0, 0xDE];
+        let packets = ["a".repeat(500), "layered".to_string()];
+
+        let mut transport = LayeredTransport::<_, 1024>::new(MaskedStream::new(vec![], KEY), 64);
+        for packet in &packets {
+            transport.write_packet::<String, _>(packet, &mut ()).await?;
+        }
+        let masked = transport.into_inner().into_inner();
+
+        // Unmasking the raw bytes leaves the frames, the large packet deflated behind its
+        // uncompressed length and the small one stored behind a data length of 0.
+        let mut unmasked = MaskedStream::new(masked.as_slice(), KEY);
+        let frame = unmasked
+            .decode_component::<LengthDelimited<ByteDrain>>()
+            .await?;
+        assert!(frame.len() < 100);
+        let mut slice = frame.as_slice();
+        assert_eq!(slice.read_var_int().await?, 502);
+        let mut body = vec![];
+        ZlibDecoder::new(slice).read_to_end(&mut body)?;
+        assert_eq!(
+            body.as_slice().decode_component::<String>().await?,
+            packets[0]
+        );
+        let frame = unmasked
+            .decode_component::<LengthDelimited<ByteDrain>>()
+            .await?;
+        assert_eq!(frame, b"\0\x07layered");
+
+        let mut transport =
+            LayeredTransport::<_, 1024>::new(MaskedStream::new(masked.as_slice(), KEY), 64);
+        for packet in &packets {
+            assert_eq!(&transport.read_packet::<String, _>(&mut ()).await?, packet);
+        }
+        let error = transport.read_packet::<String, _>(&mut ()).await;
+        assert!(error.is_err_and(|e| e.is_eof()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_layered_transport_limits() -> DraxResult<()> {
+        let mut transport = LayeredTransport::<_, 1024>::new(vec![], 64);
+        transport
+            .write_packet::<String, _>(&"a".repeat(500), &mut ())
+            .await?;
+        let bytes = transport.into_inner();
+
+        let mut transport = LayeredTransport::<_, 1024>::new(bytes.as_slice(), 1024);
+        let error = transport.read_packet::<String, _>(&mut ()).await;
+        assert_matches!(error, Err(TransportError::Rejected(_)));
+        let mut transport = LayeredTransport::<_, 256>::new(bytes.as_slice(), 64);
+        let error = transport.read_packet::<String, _>(&mut ()).await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(256, 502, _)));
+
+        let mut transport = LayeredTransport::<_, 256>::new(vec![], 64);
+        let error = transport
+            .write_packet::<String, _>(&"a".repeat(500), &mut ())
+            .await;
+        assert_matches!(error, Err(TransportError::LimitExceeded(256, 502, _)));
+        Ok(())
+    }
+}
@@ -1,4 +1,3 @@
-#![cfg_attr(feature = "slices", feature(maybe_uninit_uninit_array))]
 #![cfg_attr(test, feature(assert_matches))]
 #![allow(async_fn_in_trait)]
 
@@ -31,6 +30,13 @@ pub mod error;
 /// Provides re-exports of common types for macros.
 pub mod prelude;
 
+/// Provides reader and writer adapters used by the transport layer.
+pub mod io;
+
+/// Provides helpers for asserting custom packet components encode and decode symmetrically.
+#[cfg(feature = "test-util")]
+pub mod test_util;
+
 /// Provides packet component implementations for common types.
 pub mod delegate {
     macro_rules! decode {
@@ -64,15 +70,50 @@ pub mod delegate {
         };
     }
 
+    macro_rules! shared_without_context {
+        ($($component:ty),*) => {$(
+            impl<C: Send + Sync> $crate::prelude::PacketComponentShared<C> for $component {
+                async fn decode_shared<A: ::tokio::io::AsyncRead + Unpin + Send + Sync + ?Sized>(
+                    _: &C,
+                    read: &mut A,
+                ) -> $crate::prelude::DraxResult<Self::ComponentType> {
+                    <Self as $crate::prelude::PacketComponent<()>>::decode(&mut (), read).await
+                }
+            }
+        )*};
+    }
+
+    /// Provides delegates which compress a single component with zlib.
+    #[cfg(feature = "compression")]
+    pub mod compression;
+
+    /// Provides object safe packet components and unions dispatched through a runtime registry.
+    pub mod dynamic;
+
+    /// Provides helpers for encoding and decoding enums whose variant is selected by a tag.
+    pub mod enums;
+
+    /// Provides packet component implementations for length-delimited frames.
+    pub mod frame;
+
+    /// Provides delegates which guard decoding with limits carried by the context.
+    pub mod guard;
+
     /// Provides packet component implementations for `HashMap<K, V>`.
     pub mod map;
 
+    /// Provides delegates which convert between a wire component and a domain type.
+    pub mod mapped;
+
     /// Provides packet component implementations for `Option<T>`.
     pub mod option;
 
     /// Provides packet component implementations for primitive numeric types and `Uuid`.
     pub mod primitive;
 
+    /// Provides packet component implementations for `Range<T>` and `RangeInclusive<T>`.
+    pub mod range;
+
     /// Provides packet component implementations for `serde::Serialize` and `serde::Deserialize` values.
     #[cfg(feature = "serde")]
     pub mod serde_json;
@@ -83,6 +124,9 @@ pub mod delegate {
     /// Provides packet component implementations for `Vec<T>` and `[T; N]`.
     pub mod vec;
 
+    /// Provides delegates whose presence on the wire depends on the protocol version.
+    pub mod version;
+
     /// NBT is a tree data structure used and defined in Minecraft's protocol. This is extended to this
     /// crate to allow for easy low-level serialization and deserialization of NBT data. This entire
     /// module can be omitted by disabling the `nbt` feature.
@@ -1,16 +1,123 @@
+#[cfg(feature = "compression")]
+pub use crate::delegate::compression::Compressed;
+#[cfg(feature = "checksum")]
+pub use crate::delegate::frame::Validated;
 #[cfg(feature = "nbt")]
-pub use crate::delegate::nbt::{EnsuredCompoundTag, Tag};
+pub use crate::delegate::nbt::{
+    load_tag, size_tag, write_tag, EnsuredCompoundTag, EnsuredTag, NbtAccounter, Tag,
+};
 #[cfg(feature = "serde")]
-pub use crate::delegate::serde_json::JsonDelegate;
+pub use crate::delegate::serde_json::{DraxSerde, JsonDelegate};
 pub use crate::delegate::{
-    option::Maybe,
-    primitive::{VarInt, VarLong},
-    string::LimitedString,
-    vec::{ByteDrain, LimitedVec, SliceU8, VecU8},
+    dynamic::{
+        DynPacketComponent, DynamicBody, DynamicPacket, DynamicUnion, FnComponent, HasRegistry,
+        HasRuntimeVariants, Registry, RuntimeEnum, RuntimeVariant,
+    },
+    enums::{BoolEnum, EnumVarInt, OptionalEnum, PackedDiscriminant, TaggedVariant},
+    frame::{
+        FramedVec, HasRemaining, LengthDelimited, RawPacket, RestOfFrame, TrackedFrame,
+        TrailingDefault, U32Prefixed,
+    },
+    guard::{
+        Budgeted, CountedMap, CountedVec, DepthGuard, DepthLimited, DepthScope, ElementCounter,
+        HasDepth, HasElementCounter, HasOpBudget, HasSizeHint, HintedVec, OpBudget,
+    },
+    map::{HashMapWith, LimitedMap, OrderedMap},
+    mapped::{Checked, Codec, Mapped, Newtype, Predicate, Transparent},
+    option::{BitmaskFields, Maybe, SentinelOption},
+    primitive::{
+        read_var_int_with_first, read_var_long_with_first, size_var_int, size_var_long,
+        var_int_bytes, var_long_bytes, BincodeVarInt, BincodeVarU64, Index, LittleEndian, Ranged,
+        VarInt, VarLong, ZeroByte, I24, U24,
+    },
+    string::{
+        DelimitedString, HasStringScratch, IncrementalString, LimitedString, PrefixLength,
+        PrefixedString, ScratchString, ShortString,
+    },
+    vec::{
+        ArcSlice, ByteDrain, ByteLengthPrefixedVec, Collection, FixedVec, LimitedByteDrain,
+        LimitedVec, LimitedVecU8, ParallelArrays2, RleBytes, SliceU8, Terminated, VecU8,
+    },
+    version::{Between, HasProtocolVersion},
 };
 #[cfg(feature = "nbt")]
 pub use crate::error::NbtError;
 pub use crate::error::{DraxResult, TransportError};
+#[cfg(feature = "compression")]
+pub use crate::io::LayeredTransport;
+pub use crate::io::{CountingReader, CountingWriter, DraxBufWriter, FrameRemaining, MaskedStream};
 #[cfg(feature = "context")]
 pub use crate::transport::context::{ReaderContext, WriterContext};
-pub use crate::transport::{DraxReadExt, DraxWriteExt, PacketComponent, Size};
+#[cfg(feature = "bytes")]
+pub use crate::transport::freeze_encode;
+pub use crate::transport::{
+    decode_header_then_stream, decode_metered, decode_packet, encode_framed_metered,
+    encode_metered, measured_encode, DecodeSlice, DraxReadExt, DraxWriteExt, EncodedPacket,
+    IdentifiedPacket, Metrics, PacketComponent, PacketComponentShared, PollComponent, PollDecode,
+    Polled, Size, SizeCache, VarIntPatch,
+};
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use std::assert_matches::assert_matches;
+    use std::collections::HashMap;
+    use std::io::Cursor;
+
+    #[tokio::test]
+    pub async fn test_limited_map_from_prelude() -> DraxResult<()> {
+        let map = HashMap::from([(1, "one".to_string())]);
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<LimitedMap<VarInt, String, 4>>(&map)
+            .await?;
+        assert_eq!(cursor.get_ref().len(), size_var_int(1) * 3 + 3);
+        assert_eq!(var_int_bytes(1).1, size_var_int(1));
+
+        cursor.set_position(0);
+        let back = cursor
+            .decode_component::<LimitedMap<VarInt, String, 4>>()
+            .await?;
+        assert_eq!(back, map);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_context_delegates_from_prelude() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<Vec<Vec<u8>>>(&vec![vec![1, 2], vec![3]])
+            .await?;
+        cursor.set_position(0);
+        let mut counter = ElementCounter::new(2);
+        let back = CountedVec::<CountedVec<u8>>::decode(&mut counter, &mut cursor).await;
+        assert_matches!(back, Err(TransportError::LimitExceeded(2, 4, _)));
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<String>(&"test".to_string())
+            .await?;
+        cursor.set_position(0);
+        let back = ScratchString::<8>::decode(&mut Vec::new(), &mut cursor).await?;
+        assert_eq!(back, "test");
+        Ok(())
+    }
+
+    #[cfg(feature = "nbt")]
+    #[tokio::test]
+    pub async fn test_nbt_helpers_from_prelude() -> DraxResult<()> {
+        let tag = Tag::string("test");
+        let mut cursor = Cursor::new(vec![]);
+        write_tag(&mut cursor, &tag).await?;
+        assert_eq!(cursor.get_ref().len(), size_tag(&tag)?);
+
+        cursor.set_position(0);
+        let back = load_tag(&mut cursor, tag.get_tag_bit(), 0, &mut NbtAccounter::new(0)).await?;
+        assert_eq!(back, tag);
+
+        cursor.set_position(0);
+        let back = load_tag(&mut cursor, tag.get_tag_bit(), 0, &mut NbtAccounter::new(2)).await;
+        assert_matches!(back, Err(TransportError::NbtError(_)));
+        Ok(())
+    }
+}
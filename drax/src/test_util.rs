@@ -0,0 +1,136 @@
+use crate::prelude::{DraxResult, PacketComponent};
+use std::fmt::Debug;
+
+/// Encodes the value, decodes it back and asserts the decoded value equals the original.
+///
+/// The decode must also consume every byte produced by the encode, a component which leaves
+/// bytes behind would desynchronize the rest of the packet.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use drax::test_util::roundtrip;
+/// # async fn test() -> DraxResult<()> {
+/// roundtrip::<VarInt>(300).await?;
+/// roundtrip::<String>("test string".to_string()).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn roundtrip<P: PacketComponent<()>>(value: P::ComponentType) -> DraxResult<()>
+where
+    P::ComponentType: PartialEq + Debug,
+{
+    roundtrip_with_ctx::<P, ()>(value, &mut ()).await
+}
+
+/// Encodes the value, decodes it back with the given context and asserts the decoded value
+/// equals the original.
+///
+/// See [`roundtrip`] for the assertions made.
+pub async fn roundtrip_with_ctx<P: PacketComponent<C>, C: Send + Sync>(
+    value: P::ComponentType,
+    context: &mut C,
+) -> DraxResult<()>
+where
+    P::ComponentType: PartialEq + Debug,
+{
+    let mut buffer = Vec::new();
+    P::encode(&value, context, &mut buffer).await?;
+
+    let mut slice = buffer.as_slice();
+    let decoded = P::decode(context, &mut slice).await?;
+    assert_eq!(decoded, value, "component did not round trip");
+    assert!(
+        slice.is_empty(),
+        "component left {} of {} encoded bytes undecoded",
+        slice.len(),
+        buffer.len()
+    );
+    Ok(())
+}
+
+/// Asserts the value encodes to the same bytes every time, as required when the bytes are
+/// signed or hashed.
+///
+/// Each round decodes the previous encoding and encodes the decoded value again, so state
+/// which differs between equal values, such as the random iteration order of a `HashMap`, is
+/// caught along with nondeterminism in the encode itself.
+pub async fn assert_deterministic<P: PacketComponent<()>>(
+    value: P::ComponentType,
+) -> DraxResult<()> {
+    const ROUNDS: usize = 8;
+
+    let mut expected = Vec::new();
+    P::encode(&value, &mut (), &mut expected).await?;
+
+    for round in 1..=ROUNDS {
+        let mut slice = expected.as_slice();
+        let value = P::decode(&mut (), &mut slice).await?;
+
+        let mut buffer = Vec::new();
+        P::encode(&value, &mut (), &mut buffer).await?;
+        assert_eq!(
+            buffer, expected,
+            "component encoded differently in round {round} of {ROUNDS}"
+        );
+    }
+    Ok(())
+}
+
+/// Feeds arbitrary bytes to `P::decode`, the decode may fail but must never panic.
+///
+/// This is intended as a fuzz entry point, any panic raised by the decode propagates to the
+/// caller and is reported by the fuzzer.
+pub async fn decode_is_total<P: PacketComponent<()>>(bytes: &[u8]) {
+    let mut slice = bytes;
+    let _ = P::decode(&mut (), &mut slice).await;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{DraxResult, LengthDelimited, LimitedString, Maybe, VarInt, VarLong};
+    use crate::test_util::{assert_deterministic, decode_is_total, roundtrip, roundtrip_with_ctx};
+    use std::collections::{BTreeMap, HashMap};
+
+    #[tokio::test]
+    pub async fn test_roundtrip_delegates() -> DraxResult<()> {
+        roundtrip::<VarInt>(-8877777).await?;
+        roundtrip::<VarLong>(i64::MIN).await?;
+        roundtrip::<bool>(true).await?;
+        roundtrip::<String>("test string".to_string()).await?;
+        roundtrip::<LimitedString<16>>("limited".to_string()).await?;
+        roundtrip::<Vec<VarInt>>(vec![1, 300, -1]).await?;
+        roundtrip::<Maybe<VarInt>>(Some(300)).await?;
+        roundtrip::<Maybe<VarInt>>(None).await?;
+        roundtrip::<HashMap<String, i32>>(HashMap::from([("key".to_string(), 10)])).await?;
+        roundtrip::<LengthDelimited<String>>("framed".to_string()).await?;
+        roundtrip_with_ctx::<VarInt, _>(55324, &mut 0u8).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_decode_is_total() {
+        decode_is_total::<VarInt>(&[]).await;
+        decode_is_total::<VarInt>(&[0xFF; 6]).await;
+        decode_is_total::<i64>(&[1, 2, 3]).await;
+        decode_is_total::<String>(&[5, b'a']).await;
+        decode_is_total::<String>(&[2, 0xC3, 0x28]).await;
+        decode_is_total::<LengthDelimited<i32>>(&[2, 1]).await;
+        decode_is_total::<LengthDelimited<VarInt>>(&[3, 10, 20, 30]).await;
+    }
+
+    fn entries() -> impl Iterator<Item = (String, i32)> {
+        (0..32).map(|n| (format!("key{n}"), n))
+    }
+
+    #[tokio::test]
+    pub async fn test_btree_map_is_deterministic() -> DraxResult<()> {
+        assert_deterministic::<BTreeMap<String, i32>>(entries().collect()).await?;
+        assert_deterministic::<Vec<VarInt>>(vec![1, 300, -1]).await
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "component encoded differently")]
+    pub async fn test_hash_map_is_not_deterministic() {
+        let _ = assert_deterministic::<HashMap<String, i32>>(entries().collect()).await;
+    }
+}
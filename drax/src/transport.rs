@@ -1,7 +1,17 @@
 use crate::delegate::primitive;
-use crate::delegate::primitive::{ReadVarInt, ReadVarLong, WriteVarInt, WriteVarLong};
-use crate::prelude::DraxResult;
-use tokio::io::{AsyncRead, AsyncWrite};
+use crate::delegate::primitive::{
+    size_var_int, ReadVarInt, ReadVarIntOpt, ReadVarLong, ReadVarLongOpt, WriteVarInt, WriteVarLong,
+};
+use crate::io::{CountingReader, CountingWriter, TeeReader};
+use crate::prelude::{DraxResult, TransportError};
+use pin_project_lite::pin_project;
+use std::borrow::Borrow;
+use std::future::Future;
+use std::io::SeekFrom;
+use std::marker::{PhantomData, PhantomPinned};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt};
 
 /// Declares the size in bytes of a packet component.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -14,6 +24,52 @@ pub enum Size {
     Constant(usize),
 }
 
+impl Size {
+    /// Returns the number of bytes declared, regardless of whether the size is constant.
+    pub fn bytes(&self) -> usize {
+        match self {
+            Size::Dynamic(x) | Size::Constant(x) => *x,
+        }
+    }
+
+    /// Returns the number of bytes declared, this is the same as [`Size::bytes`].
+    pub fn as_usize(&self) -> usize {
+        self.bytes()
+    }
+
+    /// Adds two sizes, returning `TransportError::SizeOverflow` instead of saturating if the
+    /// total does not fit in a `usize`.
+    pub fn checked_add(self, rhs: Size) -> DraxResult<Size> {
+        match (self, rhs) {
+            (Size::Dynamic(x), Size::Dynamic(y))
+            | (Size::Dynamic(x), Size::Constant(y))
+            | (Size::Constant(x), Size::Dynamic(y)) => x.checked_add(y).map(Size::Dynamic),
+            (Size::Constant(x), Size::Constant(y)) => x.checked_add(y).map(Size::Constant),
+        }
+        .ok_or(TransportError::SizeOverflow)
+    }
+}
+
+/// Sizes are ordered by their byte count. A dynamic size orders before a constant size of the
+/// same byte count so the ordering agrees with `Eq`, compare [`Size::bytes`] to ignore the
+/// discriminant entirely.
+impl Ord for Size {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let discriminant = |size: &Size| matches!(size, Size::Constant(_));
+        self.bytes()
+            .cmp(&other.bytes())
+            .then_with(|| discriminant(self).cmp(&discriminant(other)))
+    }
+}
+
+impl PartialOrd for Size {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Sizes saturate at `usize::MAX` rather than wrapping, use [`Size::checked_add`] to detect
+/// the overflow.
 impl std::ops::Add for Size {
     type Output = Size;
 
@@ -21,8 +77,8 @@ impl std::ops::Add for Size {
         match (self, rhs) {
             (Size::Dynamic(x), Size::Dynamic(y))
             | (Size::Dynamic(x), Size::Constant(y))
-            | (Size::Constant(x), Size::Dynamic(y)) => Size::Dynamic(x + y),
-            (Size::Constant(x), Size::Constant(y)) => Size::Constant(x + y),
+            | (Size::Constant(x), Size::Dynamic(y)) => Size::Dynamic(x.saturating_add(y)),
+            (Size::Constant(x), Size::Constant(y)) => Size::Constant(x.saturating_add(y)),
         }
     }
 }
@@ -32,17 +88,89 @@ impl std::ops::Add<usize> for Size {
 
     fn add(self, rhs: usize) -> Self::Output {
         match self {
-            Size::Dynamic(x) | Size::Constant(x) => Size::Dynamic(x + rhs),
+            Size::Dynamic(x) | Size::Constant(x) => Size::Dynamic(x.saturating_add(rhs)),
+        }
+    }
+}
+
+/// A bare byte count carries no guarantee of being constant, it converts to a dynamic size.
+impl From<usize> for Size {
+    fn from(value: usize) -> Self {
+        Size::Dynamic(value)
+    }
+}
+
+impl From<Size> for usize {
+    fn from(value: Size) -> Self {
+        value.bytes()
+    }
+}
+
+/// Sizes a packet component, skipping the size traversal when its size is fixed.
+///
+/// The size is taken from `P::FIXED_SIZE` when declared, otherwise the input is measured on
+/// every call. A `Size::Constant` reported by `size` only holds for the value it was reported
+/// for, `Maybe<T>` reports different constants for `None` and `Some`, so it is never reused for
+/// another input.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # fn test() -> DraxResult<()> {
+/// let mut cache = SizeCache::<i64>::new();
+/// assert_eq!(cache.size(&10, &mut ())?, Size::Constant(8));
+/// assert_eq!(cache.size(&20, &mut ())?, Size::Constant(8));
+/// # Ok(())
+/// # }
+/// ```
+pub struct SizeCache<P> {
+    _phantom: PhantomData<P>,
+}
+
+impl<P> SizeCache<P> {
+    pub fn new() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the size of the input, using the fixed size of the component if it declares one.
+    pub fn size<C: Send + Sync>(
+        &mut self,
+        input: &P::ComponentType,
+        context: &mut C,
+    ) -> DraxResult<Size>
+    where
+        P: PacketComponent<C>,
+    {
+        if let Some(size) = P::FIXED_SIZE {
+            return Ok(Size::Constant(size));
         }
+        P::size(input, context)
+    }
+}
+
+impl<P> Default for SizeCache<P> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 /// Defines a structure that can be encoded and decoded.
+///
+/// This is the trait implemented by symmetric components, it provides the
+/// [`DecodePacketComponent`], [`EncodePacketComponent`] and [`SizedPacketComponent`] halves
+/// through blanket implementations.
 pub trait PacketComponent<C: Send + Sync> {
     /// The type which the packet component is responsible for
     /// representing during reading and writing.
     type ComponentType: Sized + Send + Sync;
 
+    /// The size of the component if every value encodes to the same number of bytes.
+    ///
+    /// Implementations which declare a fixed size allow callers such as [`SizeCache`] to skip
+    /// the `size` call entirely. The declared size must match what `size` would return.
+    const FIXED_SIZE: Option<usize> = None;
+
     /// Decodes the packet component from the given reader.
     async fn decode<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
         context: &mut C,
@@ -60,6 +188,612 @@ pub trait PacketComponent<C: Send + Sync> {
     fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size>;
 }
 
+/// A packet component encoded once ahead of time, which can then be written to any number of
+/// writers without being sized or encoded again.
+///
+/// This is meant for broadcasting the same packet to many connections. The context type used to
+/// encode the packet is part of the type, so a packet encoded for one context cannot be passed
+/// where a packet encoded for another context is expected.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # async fn test() -> DraxResult<()> {
+/// let packet = EncodedPacket::<String, ()>::new(&"hello".to_string(), &mut ()).await?;
+/// let mut first = vec![];
+/// let mut second = vec![];
+/// packet.write_to(&mut first).await?;
+/// packet.write_to(&mut second).await?;
+/// assert_eq!(first, second);
+/// # Ok(())
+/// # }
+/// ```
+pub struct EncodedPacket<P, C> {
+    bytes: Vec<u8>,
+    _phantom: PhantomData<fn() -> (P, C)>,
+}
+
+impl<P: PacketComponent<C>, C: Send + Sync> EncodedPacket<P, C> {
+    /// Sizes and encodes the component, returning a `TransportError::SizeMismatch` if the
+    /// component encodes a different number of bytes than its `size` declared.
+    pub async fn new(component: &P::ComponentType, context: &mut C) -> DraxResult<Self> {
+        let size = P::size(component, context)?.bytes();
+
+        let mut bytes = Vec::with_capacity(size);
+        P::encode(component, context, &mut bytes).await?;
+        if bytes.len() != size {
+            return TransportError::size_mismatch(size, bytes.len());
+        }
+        Ok(Self {
+            bytes,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+impl<P, C> EncodedPacket<P, C> {
+    /// Writes the encoded bytes to the given writer.
+    pub async fn write_to<W: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+        &self,
+        write: &mut W,
+    ) -> DraxResult<()> {
+        write.write_all(&self.bytes).await?;
+        Ok(())
+    }
+
+    /// Returns the encoded bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// Sizes and encodes the component into reference counted `Bytes`, which can be cloned and
+/// handed to any number of writer tasks without copying the encoded packet.
+///
+/// Like [`EncodedPacket::new`] a `TransportError::SizeMismatch` is returned if the component
+/// encodes a different number of bytes than its `size` declared.
+#[cfg(feature = "bytes")]
+pub async fn freeze_encode<P: PacketComponent<C>, C: Send + Sync>(
+    component: &P::ComponentType,
+    context: &mut C,
+) -> DraxResult<bytes::Bytes> {
+    let packet = EncodedPacket::<P, C>::new(component, context).await?;
+    Ok(bytes::Bytes::from(packet.bytes))
+}
+
+/// A VarInt placeholder reserved by [`DraxWriteExt::reserve_var_int`], rewritten in place once
+/// the value is known.
+///
+/// The placeholder always occupies the maximum 5 bytes, the value is written padded to that
+/// width with continuation bits, which every VarInt reader accepts.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # use std::io::Cursor;
+/// # async fn test() -> DraxResult<()> {
+/// let mut cursor = Cursor::new(vec![]);
+/// let patch = cursor.reserve_var_int().await?;
+/// cursor.encode_component::<String>(&"body".to_string()).await?;
+/// patch.finish(&mut cursor, 5).await?;
+///
+/// cursor.set_position(0);
+/// assert_eq!(cursor.read_var_int().await?, 5);
+/// assert_eq!(cursor.decode_component::<String>().await?, "body");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct VarIntPatch {
+    position: u64,
+}
+
+impl VarIntPatch {
+    const WIDTH: usize = 5;
+
+    fn padded(value: i32) -> [u8; Self::WIDTH] {
+        let value = value as u32;
+        let mut bytes = [0; Self::WIDTH];
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            *byte = ((value >> (7 * index)) & 0x7F) as u8;
+            if index < Self::WIDTH - 1 {
+                *byte |= 0x80;
+            }
+        }
+        bytes
+    }
+
+    /// Returns the position of the placeholder in the writer.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Rewrites the placeholder with the given value, restoring the position of the writer
+    /// afterwards so writing can continue after the body.
+    pub async fn finish<W: AsyncWrite + AsyncSeek + Unpin + Send + Sync + ?Sized>(
+        self,
+        write: &mut W,
+        value: i32,
+    ) -> DraxResult<()> {
+        let end = write.stream_position().await?;
+        write.seek(SeekFrom::Start(self.position)).await?;
+        write.write_all(&Self::padded(value)).await?;
+        write.seek(SeekFrom::Start(end)).await?;
+        Ok(())
+    }
+}
+
+/// Defines the decoding half of a packet component.
+///
+/// Every [`PacketComponent`] implements this trait, components which are only ever read, such as
+/// a packet captured for logging, can implement it on its own. Bounds which only decode should
+/// prefer this trait over `PacketComponent`.
+pub trait DecodePacketComponent<C: Send + Sync> {
+    /// The type which is produced when decoding the component.
+    type ComponentType: Sized + Send + Sync;
+
+    /// Decodes the packet component from the given reader.
+    async fn decode<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+        context: &mut C,
+        read: &mut A,
+    ) -> DraxResult<Self::ComponentType>;
+}
+
+/// Defines the encoding half of a packet component.
+///
+/// Every [`PacketComponent`] implements this trait, components which are only ever written, such
+/// as a computed field, can implement it on its own.
+pub trait EncodePacketComponent<C: Send + Sync> {
+    /// The type which is consumed when encoding the component.
+    type ComponentType: Sized + Send + Sync;
+
+    /// Encodes the packet component to the given writer.
+    async fn encode<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+        component_ref: &Self::ComponentType,
+        context: &mut C,
+        write: &mut A,
+    ) -> DraxResult<()>;
+}
+
+/// Defines the size calculation of an encodable packet component.
+pub trait SizedPacketComponent<C: Send + Sync>: EncodePacketComponent<C> {
+    /// Calculates the size of the packet component.
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size>;
+}
+
+impl<C: Send + Sync, P: PacketComponent<C>> DecodePacketComponent<C> for P {
+    type ComponentType = <P as PacketComponent<C>>::ComponentType;
+
+    async fn decode<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+        context: &mut C,
+        read: &mut A,
+    ) -> DraxResult<Self::ComponentType> {
+        <P as PacketComponent<C>>::decode(context, read).await
+    }
+}
+
+impl<C: Send + Sync, P: PacketComponent<C>> EncodePacketComponent<C> for P {
+    type ComponentType = <P as PacketComponent<C>>::ComponentType;
+
+    async fn encode<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+        component_ref: &Self::ComponentType,
+        context: &mut C,
+        write: &mut A,
+    ) -> DraxResult<()> {
+        <P as PacketComponent<C>>::encode(component_ref, context, write).await
+    }
+}
+
+impl<C: Send + Sync, P: PacketComponent<C>> SizedPacketComponent<C> for P {
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        <P as PacketComponent<C>>::size(input, context)
+    }
+}
+
+/// Defines a packet component which can be decoded with a shared reference to the context.
+///
+/// Since `PacketComponent::decode` takes the context mutably, two fields which both need the
+/// context cannot be decoded at the same time. Components whose decode only reads the context
+/// can implement this trait, allowing independent fields to be decoded concurrently, for example
+/// with `tokio::join!`. Components which mutate the context while decoding cannot implement it.
+pub trait PacketComponentShared<C: Send + Sync>: PacketComponent<C> {
+    /// Decodes the packet component from the given reader without mutating the context.
+    async fn decode_shared<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+        context: &C,
+        read: &mut A,
+    ) -> DraxResult<Self::ComponentType>;
+}
+
+/// Defines a packet component which can be decoded synchronously from an in-memory slice.
+///
+/// This is a fast path for callers holding an entire packet in memory, each decode returns the
+/// value along with the rest of the slice so several values can be decoded one after another.
+/// A slice ending before the value is complete fails with `TransportError::EOF`.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # fn test() -> DraxResult<()> {
+/// let bytes = [172, 2, 0, 0, 0, 7];
+/// let (first, rest) = VarInt::decode_slice(&bytes)?;
+/// let (second, rest) = i32::decode_slice(rest)?;
+/// assert_eq!((first, second), (300, 7));
+/// assert!(rest.is_empty());
+/// # Ok(())
+/// # }
+/// ```
+pub trait DecodeSlice: PacketComponent<()> {
+    /// Decodes the packet component from the front of the slice, returning it along with the
+    /// remaining bytes.
+    fn decode_slice(bytes: &[u8]) -> DraxResult<(Self::ComponentType, &[u8])>;
+}
+
+/// Defines a packet component whose decode is driven by hand through `poll_decode`, like the
+/// VarInt readers.
+///
+/// A decode written as an `async fn` keeps its progress in the compiler generated future, which
+/// cannot be inspected or reused. Implementors of this trait keep their progress in an explicit
+/// `State` instead, so a decode interrupted by a reader returning `Poll::Pending` resumes exactly
+/// where it left off without any allocation or buffering. Implementors are used as packet
+/// components through the [`Polled`] delegate.
+pub trait PollComponent<C: Send + Sync> {
+    /// The type which the packet component is responsible for representing.
+    type ComponentType: Sized + Send + Sync;
+
+    /// The progress of a decode, starting from its default.
+    type State: Default + Unpin + Send + Sync;
+
+    /// The size of the component if every value encodes to the same number of bytes.
+    const FIXED_SIZE: Option<usize> = None;
+
+    /// Advances the decode, returning `Poll::Pending` if the reader has no more data available.
+    ///
+    /// The same state is passed to every call until the decode completes.
+    fn poll_decode<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+        state: &mut Self::State,
+        context: &mut C,
+        cx: &mut Context<'_>,
+        read: &mut A,
+    ) -> Poll<DraxResult<Self::ComponentType>>;
+
+    /// Encodes the packet component to the given writer.
+    async fn encode<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+        component_ref: &Self::ComponentType,
+        context: &mut C,
+        write: &mut A,
+    ) -> DraxResult<()>;
+
+    /// Calculates the size of the packet component.
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size>;
+}
+
+pin_project! {
+    /// The future decoding a [`PollComponent`] by repeatedly calling `poll_decode`.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct PollDecode<'a, P, C, A: ?Sized>
+    where
+        P: PollComponent<C>,
+        C: Send,
+        C: Sync,
+    {
+        state: P::State,
+        context: &'a mut C,
+        reader: &'a mut A,
+        // Make this future `!Unpin` for compatibility with async trait methods.
+        #[pin]
+        _pin: PhantomPinned,
+    }
+}
+
+impl<'a, P: PollComponent<C>, C: Send + Sync, A: ?Sized> PollDecode<'a, P, C, A> {
+    pub fn new(context: &'a mut C, reader: &'a mut A) -> Self {
+        Self {
+            state: P::State::default(),
+            context,
+            reader,
+            _pin: PhantomPinned,
+        }
+    }
+}
+
+impl<P: PollComponent<C>, C: Send + Sync, A> Future for PollDecode<'_, P, C, A>
+where
+    A: AsyncRead + Unpin + Send + Sync + ?Sized,
+{
+    type Output = DraxResult<P::ComponentType>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.project();
+        P::poll_decode(me.state, me.context, cx, me.reader)
+    }
+}
+
+/// A delegate struct which bridges a [`PollComponent`] to a [`PacketComponent`].
+pub struct Polled<P>(PhantomData<P>);
+
+impl<C: Send + Sync, P: PollComponent<C>> PacketComponent<C> for Polled<P> {
+    type ComponentType = P::ComponentType;
+
+    const FIXED_SIZE: Option<usize> = P::FIXED_SIZE;
+
+    async fn decode<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+        context: &mut C,
+        read: &mut A,
+    ) -> DraxResult<Self::ComponentType> {
+        PollDecode::<P, C, A>::new(context, read).await
+    }
+
+    async fn encode<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+        component_ref: &Self::ComponentType,
+        context: &mut C,
+        write: &mut A,
+    ) -> DraxResult<()> {
+        P::encode(component_ref, context, write).await
+    }
+
+    fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+        P::size(input, context)
+    }
+}
+
+/// Defines a packet component which describes its own protocol-level packet id.
+///
+/// The id is written as a VarInt before the packet body, allowing the receiving side to
+/// dispatch on the id before decoding the rest of the packet.
+pub trait IdentifiedPacket<C: Send + Sync>: PacketComponent<C> {
+    /// The wire id of the packet.
+    const PACKET_ID: i32;
+
+    /// Encodes the packet id followed by the packet body.
+    async fn encode_with_id<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+        component_ref: &Self::ComponentType,
+        context: &mut C,
+        write: &mut A,
+    ) -> DraxResult<()> {
+        write.write_var_int(Self::PACKET_ID).await?;
+        Self::encode(component_ref, context, write).await
+    }
+
+    /// Decodes the packet id, rejecting any id other than `PACKET_ID`, followed by the packet
+    /// body.
+    async fn decode_expecting_id<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+        context: &mut C,
+        read: &mut A,
+    ) -> DraxResult<Self::ComponentType> {
+        let packet_id = read.read_var_int().await?;
+        if packet_id != Self::PACKET_ID {
+            return TransportError::unexpected_packet_id(Self::PACKET_ID, packet_id);
+        }
+        Self::decode(context, read).await
+    }
+}
+
+/// Receives statistics from the codec and framing helpers.
+///
+/// A context implements this trait to observe traffic, every hook defaults to a no-op so
+/// implementors only override the hooks they're interested in. The hooks are only called by the
+/// metered helpers such as [`decode_metered`] and [`encode_metered`], the regular helpers are left
+/// untouched.
+pub trait Metrics {
+    /// Called with the number of bytes read while decoding a packet.
+    fn on_bytes_read(&mut self, _n: usize) {}
+
+    /// Called with the number of bytes written while encoding a packet.
+    fn on_bytes_written(&mut self, _n: usize) {}
+
+    /// Called after a packet was successfully decoded.
+    fn on_packet_decoded(&mut self) {}
+
+    /// Called after a packet was successfully encoded.
+    fn on_packet_encoded(&mut self) {}
+}
+
+impl Metrics for () {}
+
+/// Decodes the packet component from a frame of `declared_len` bytes, discarding whatever the
+/// component leaves unread.
+///
+/// This is the lenient counterpart of [`DraxReadExt::decode_framed`] for forward compatible
+/// protocols, trailing fields appended by a newer peer are skipped rather than rejected with
+/// `TransportError::TrailingBytes`. The component cannot read past the frame, and a reader
+/// ending before the frame does fails with `TransportError::EOF`.
+pub async fn decode_packet<P, C, A>(
+    context: &mut C,
+    read: &mut A,
+    declared_len: u64,
+) -> DraxResult<P::ComponentType>
+where
+    P: PacketComponent<C>,
+    C: Send + Sync,
+    A: AsyncRead + Unpin + Send + Sync + ?Sized,
+{
+    let mut limited = read.take(declared_len);
+    let component = P::decode(context, &mut limited).await?;
+    tokio::io::copy(&mut limited, &mut tokio::io::sink()).await?;
+    if limited.limit() != 0 {
+        return Err(TransportError::EOF);
+    }
+    Ok(component)
+}
+
+/// Decodes the header component `H` and hands the reader back positioned at the body which
+/// follows it.
+///
+/// Packets carrying a large payload after a small header, such as file transfers, can then
+/// stream the body from the returned reader rather than buffering it whole. Nothing past the
+/// header is read.
+pub async fn decode_header_then_stream<'a, H, C, A>(
+    context: &mut C,
+    read: &'a mut A,
+) -> DraxResult<(H::ComponentType, &'a mut A)>
+where
+    H: PacketComponent<C>,
+    C: Send + Sync,
+    A: AsyncRead + Unpin + Send + Sync + ?Sized,
+{
+    let header = H::decode(context, read).await?;
+    Ok((header, read))
+}
+
+/// Decodes the packet component, reporting the bytes read and the decoded packet to the context.
+///
+/// Bytes consumed by a failed decode are still reported.
+pub async fn decode_metered<P, C, A>(context: &mut C, read: &mut A) -> DraxResult<P::ComponentType>
+where
+    P: PacketComponent<C>,
+    C: Metrics + Send + Sync,
+    A: AsyncRead + Unpin + Send + Sync + ?Sized,
+{
+    let mut counted = CountingReader::new(read);
+    let result = P::decode(context, &mut counted).await;
+    context.on_bytes_read(counted.count() as usize);
+    if result.is_ok() {
+        context.on_packet_decoded();
+    }
+    result
+}
+
+/// Encodes the packet component, reporting the bytes written and the encoded packet to the
+/// context.
+///
+/// Bytes written by a failed encode are still reported.
+pub async fn encode_metered<P, C, A>(
+    component: &P::ComponentType,
+    context: &mut C,
+    write: &mut A,
+) -> DraxResult<()>
+where
+    P: PacketComponent<C>,
+    C: Metrics + Send + Sync,
+    A: AsyncWrite + Unpin + Send + Sync + ?Sized,
+{
+    let mut counted = CountingWriter::new(write);
+    let result = P::encode(component, context, &mut counted).await;
+    context.on_bytes_written(counted.count() as usize);
+    if result.is_ok() {
+        context.on_packet_encoded();
+    }
+    result
+}
+
+/// Encodes the packet component prefixed by its VarInt size, reporting the bytes written and the
+/// encoded packet to the context.
+///
+/// See [`DraxWriteExt::encode_framed`] for the framing semantics.
+pub async fn encode_framed_metered<P, C, A>(
+    component: &P::ComponentType,
+    context: &mut C,
+    write: &mut A,
+) -> DraxResult<()>
+where
+    P: PacketComponent<C>,
+    C: Metrics + Send + Sync,
+    A: AsyncWrite + Unpin + Send + Sync + ?Sized,
+{
+    let buffer = frame_component::<P, C>(component, context).await?;
+    write.write_all(&buffer).await?;
+    context.on_bytes_written(buffer.len());
+    context.on_packet_encoded();
+    Ok(())
+}
+
+/// Encodes the packet component into a new buffer, returning the buffer along with the number
+/// of bytes encoded.
+///
+/// Unlike `size` the length is measured from the actual encode, so it stays authoritative for
+/// hand-written components whose `size` is missing or wrong.
+///
+/// ```rust
+/// # use drax::prelude::*;
+/// # async fn test() -> DraxResult<()> {
+/// let (bytes, len) = measured_encode::<VarInt, ()>(&300, &mut ()).await?;
+/// assert_eq!(bytes, vec![172, 2]);
+/// assert_eq!(len, 2);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn measured_encode<P: PacketComponent<C>, C: Send + Sync>(
+    component: &P::ComponentType,
+    context: &mut C,
+) -> DraxResult<(Vec<u8>, usize)> {
+    let mut buffer = Vec::new();
+    P::encode(component, context, &mut buffer).await?;
+    let len = buffer.len();
+    Ok((buffer, len))
+}
+
+/// Scratch buffers larger than this are dropped rather than kept for reuse.
+const MAX_SCRATCH_CAPACITY: usize = 1 << 20;
+
+thread_local! {
+    static SCRATCH: std::cell::Cell<Vec<u8>> = const { std::cell::Cell::new(Vec::new()) };
+}
+
+/// Takes the scratch buffer of the current thread, the buffer is empty but keeps the capacity
+/// of previous encodes.
+pub(crate) fn take_scratch() -> Vec<u8> {
+    SCRATCH.take()
+}
+
+/// Returns a scratch buffer to the current thread for reuse.
+pub(crate) fn return_scratch(mut buffer: Vec<u8>) {
+    if buffer.capacity() <= MAX_SCRATCH_CAPACITY {
+        buffer.clear();
+        SCRATCH.set(buffer);
+    }
+}
+
+async fn frame_component<P: PacketComponent<C>, C: Send + Sync>(
+    component: &P::ComponentType,
+    context: &mut C,
+) -> DraxResult<Vec<u8>> {
+    let size = match P::size(component, context)? {
+        Size::Dynamic(x) | Size::Constant(x) => x,
+    };
+    let prefix_size = size_var_int(size as i32);
+
+    let mut buffer = Vec::with_capacity(prefix_size + size);
+    buffer.write_var_int(size as i32).await?;
+    P::encode(component, context, &mut buffer).await?;
+
+    let encoded = buffer.len() - prefix_size;
+    if encoded != size {
+        return TransportError::size_mismatch(size, encoded);
+    }
+    Ok(buffer)
+}
+
+/// Wraps a codec future in a span tagged with the component type name, recording the error on
+/// the span if the future fails. Without the `tracing` feature the future is awaited directly.
+macro_rules! instrumented {
+    ($direction:literal, $component:ty, $exec:expr) => {{
+        #[cfg(feature = "tracing")]
+        let result = traced(
+            tracing::debug_span!(
+                $direction,
+                component = std::any::type_name::<$component>(),
+                error = tracing::field::Empty,
+            ),
+            $exec,
+        )
+        .await;
+        #[cfg(not(feature = "tracing"))]
+        let result = $exec.await;
+        result
+    }};
+}
+
+#[cfg(feature = "tracing")]
+async fn traced<T>(
+    span: tracing::Span,
+    future: impl std::future::Future<Output = DraxResult<T>>,
+) -> DraxResult<T> {
+    use tracing::Instrument;
+
+    let result = future.instrument(span.clone()).await;
+    if let Err(error) = &result {
+        span.record("error", tracing::field::display(error));
+    }
+    result
+}
+
 /// An extension trait which allows for quickly accessing component reading from
 /// tokio AsyncRead types.
 pub trait DraxReadExt {
@@ -67,13 +801,59 @@ pub trait DraxReadExt {
 
     fn read_var_long(&mut self) -> ReadVarLong<'_, Self>;
 
-    async fn decode_component<P: PacketComponent<()> + Sized>(
+    /// Reads a VarInt, returning `None` if the stream ends cleanly before the first byte.
+    fn read_var_int_opt(&mut self) -> ReadVarIntOpt<'_, Self>;
+
+    /// Reads a VarLong, returning `None` if the stream ends cleanly before the first byte.
+    fn read_var_long_opt(&mut self) -> ReadVarLongOpt<'_, Self>;
+
+    /// Reads exactly `len` bytes, returning a `TransportError::LimitExceeded` without allocating
+    /// if `len` is greater than `max`.
+    ///
+    /// The length-prefixed byte decoders go through this method so the length taken from the
+    /// wire is checked before the buffer is allocated. The check is only as tight as the `max`
+    /// each caller passes, `VecU8` accepts up to `i32::MAX` bytes while [`LimitedVecU8`] takes
+    /// an explicit bound.
+    ///
+    /// [`LimitedVecU8`]: crate::delegate::vec::LimitedVecU8
+    async fn read_exact_limited(&mut self, len: usize, max: usize) -> DraxResult<Vec<u8>>;
+
+    async fn decode_component<P: DecodePacketComponent<()> + Sized>(
         &mut self,
     ) -> DraxResult<P::ComponentType>;
 
     async fn decode_own_component<P: PacketComponent<(), ComponentType = P> + Sized>(
         &mut self,
     ) -> DraxResult<P>;
+
+    /// Decodes a component, returning it along with the exact bytes it was decoded from.
+    ///
+    /// The bytes can be forwarded or logged as received, re-encoding the component is not
+    /// guaranteed to reproduce them byte for byte.
+    async fn decode_component_with_bytes<P: DecodePacketComponent<()> + Sized>(
+        &mut self,
+    ) -> DraxResult<(P::ComponentType, Vec<u8>)>;
+
+    /// Decodes a component, or returns its default if the reader is at a clean EOF.
+    ///
+    /// A clean EOF means the stream ended before a single byte of the component was read, this
+    /// allows optional trailing fields to be appended to a packet. If the stream ends part way
+    /// through the component the error is returned as usual.
+    async fn decode_or_default<P: PacketComponent<()> + Sized>(
+        &mut self,
+    ) -> DraxResult<P::ComponentType>
+    where
+        P::ComponentType: Default;
+
+    /// Decodes a component prefixed by its VarInt size, as written by
+    /// [`DraxWriteExt::encode_framed`].
+    ///
+    /// The component is decoded from a reader bounded to the frame, a zero length frame decodes
+    /// the component from no bytes at all rather than waiting on the underlying reader. If the
+    /// component does not consume the entire frame a `TransportError::TrailingBytes` is returned.
+    async fn decode_framed<P: PacketComponent<()> + Sized>(
+        &mut self,
+    ) -> DraxResult<P::ComponentType>;
 }
 
 impl<T> DraxReadExt for T
@@ -88,16 +868,80 @@ where
         primitive::read_var_long(self)
     }
 
-    async fn decode_component<P: PacketComponent<()> + Sized>(
+    fn read_var_int_opt(&mut self) -> ReadVarIntOpt<'_, Self> {
+        primitive::read_var_int_opt(self)
+    }
+
+    fn read_var_long_opt(&mut self) -> ReadVarLongOpt<'_, Self> {
+        primitive::read_var_long_opt(self)
+    }
+
+    async fn read_exact_limited(&mut self, len: usize, max: usize) -> DraxResult<Vec<u8>> {
+        if len > max {
+            return TransportError::limit_exceeded(
+                i32::try_from(max).unwrap_or(i32::MAX),
+                i32::try_from(len).unwrap_or(i32::MAX),
+                "reading bytes",
+            );
+        }
+
+        let mut buf = vec![0; len];
+        self.read_exact(&mut buf).await?;
+        Ok(buf)
+    }
+
+    async fn decode_component<P: DecodePacketComponent<()> + Sized>(
         &mut self,
     ) -> DraxResult<P::ComponentType> {
-        P::decode(&mut (), self).await
+        instrumented!("decode", P, P::decode(&mut (), self))
     }
 
     async fn decode_own_component<P: PacketComponent<(), ComponentType = P> + Sized>(
         &mut self,
     ) -> DraxResult<P> {
-        P::decode(&mut (), self).await
+        instrumented!("decode", P, P::decode(&mut (), self))
+    }
+
+    async fn decode_component_with_bytes<P: DecodePacketComponent<()> + Sized>(
+        &mut self,
+    ) -> DraxResult<(P::ComponentType, Vec<u8>)> {
+        let mut tee = TeeReader::new(self);
+        let component = instrumented!("decode", P, P::decode(&mut (), &mut tee))?;
+        Ok((component, tee.into_parts().1))
+    }
+
+    async fn decode_or_default<P: PacketComponent<()> + Sized>(
+        &mut self,
+    ) -> DraxResult<P::ComponentType>
+    where
+        P::ComponentType: Default,
+    {
+        let mut counted = CountingReader::new(self);
+        match instrumented!("decode", P, P::decode(&mut (), &mut counted)) {
+            Err(error) if error.is_eof() && counted.count() == 0 => Ok(Default::default()),
+            result => result,
+        }
+    }
+
+    async fn decode_framed<P: PacketComponent<()> + Sized>(
+        &mut self,
+    ) -> DraxResult<P::ComponentType> {
+        instrumented!("decode", P, async {
+            let len = self.read_var_int().await?;
+            if len < 0 {
+                return TransportError::limit_exceeded(0, len, "decoding frame length");
+            }
+            if len == 0 {
+                return P::decode(&mut (), &mut &[][..]).await;
+            }
+
+            let mut limited = self.take(len as u64);
+            let component = P::decode(&mut (), &mut limited).await?;
+            if limited.limit() != 0 {
+                return TransportError::trailing_bytes(limited.limit());
+            }
+            Ok(component)
+        })
     }
 }
 
@@ -108,47 +952,181 @@ pub trait DraxWriteExt {
 
     fn write_var_long(&mut self, value: i64) -> WriteVarLong<'_, Self>;
 
-    async fn encode_component<P: PacketComponent<()>>(
+    /// Writes a maximum width VarInt placeholder to be patched once the body following it has
+    /// been written, avoiding a separate size pass for seekable writers such as a `Cursor`.
+    async fn reserve_var_int(&mut self) -> DraxResult<VarIntPatch>
+    where
+        Self: AsyncSeek;
+
+    /// Encodes the component, which may also be passed from behind another reference such as
+    /// the `&&T` produced by iterator adaptors.
+    ///
+    /// The writer is not flushed, over a buffered writer the bytes may not reach the peer until
+    /// it is. Use [`DraxWriteExt::encode_component_flush`] when the peer awaits a reply.
+    async fn encode_component<P: EncodePacketComponent<()>>(
         &mut self,
-        component: &P::ComponentType,
+        component: &(impl Borrow<P::ComponentType> + ?Sized + Sync),
     ) -> DraxResult<()>;
 
+    /// Encodes a component which is its own component type.
+    ///
+    /// Like [`DraxWriteExt::encode_component`] the writer is not flushed.
     async fn encode_own_component<P: PacketComponent<(), ComponentType = P>>(
         &mut self,
         component: &P,
     ) -> DraxResult<()>;
+
+    /// Encodes the component and then flushes the writer, so the bytes reach the peer even
+    /// when the writer buffers them.
+    async fn encode_component_flush<P: EncodePacketComponent<()>>(
+        &mut self,
+        component: &(impl Borrow<P::ComponentType> + ?Sized + Sync),
+    ) -> DraxResult<()>;
+
+    /// Encodes the component, flushes the writer and shuts it down, consuming it.
+    ///
+    /// This is the "send one packet and disconnect" pattern, such as a status ping, where the
+    /// peer only sees the packet and the end of the stream once the writer is flushed and shut
+    /// down.
+    async fn encode_component_and_close<P: EncodePacketComponent<()>>(
+        self,
+        component: &(impl Borrow<P::ComponentType> + ?Sized + Sync),
+    ) -> DraxResult<()>
+    where
+        Self: Sized;
+
+    /// Encodes the slice prefixed by its VarInt length, producing the same bytes as encoding an
+    /// equivalent `Vec<T>` without requiring the elements to be owned by a `Vec`.
+    async fn encode_slice<P: PacketComponent<()>>(
+        &mut self,
+        slice: &[P::ComponentType],
+    ) -> DraxResult<()>;
+
+    /// Encodes the component prefixed by its VarInt length without calling `size`.
+    ///
+    /// The body is encoded into a scratch buffer reused across calls on the same thread, the
+    /// length of the buffer is then written as the prefix followed by the buffer. This avoids a
+    /// separate size traversal for components whose size is as expensive as their encode, and
+    /// the prefix always agrees with the body.
+    async fn encode_with_len_prefix<P: PacketComponent<()>>(
+        &mut self,
+        component: &P::ComponentType,
+    ) -> DraxResult<()>;
+
+    /// Encodes the component prefixed by its VarInt size.
+    ///
+    /// The size is computed once and used both as the prefix and to reserve the frame buffer,
+    /// the entire frame is then written with a single `write_all`. If the component encodes a
+    /// different number of bytes than its `size` declared a `TransportError::SizeMismatch` is
+    /// returned and nothing is written.
+    async fn encode_framed<P: PacketComponent<()>>(
+        &mut self,
+        component: &P::ComponentType,
+    ) -> DraxResult<()>;
 }
 
-impl<T> DraxWriteExt for T
-where
-    T: AsyncWrite + Unpin + Send + Sync + ?Sized,
-{
-    fn write_var_int(&mut self, value: i32) -> WriteVarInt<'_, Self> {
-        primitive::write_var_int(self, value)
+impl<T> DraxWriteExt for T
+where
+    T: AsyncWrite + Unpin + Send + Sync + ?Sized,
+{
+    fn write_var_int(&mut self, value: i32) -> WriteVarInt<'_, Self> {
+        primitive::write_var_int(self, value)
+    }
+
+    fn write_var_long(&mut self, value: i64) -> WriteVarLong<'_, Self> {
+        primitive::write_var_long(self, value)
+    }
+
+    async fn reserve_var_int(&mut self) -> DraxResult<VarIntPatch>
+    where
+        Self: AsyncSeek,
+    {
+        let position = self.stream_position().await?;
+        self.write_all(&VarIntPatch::padded(0)).await?;
+        Ok(VarIntPatch { position })
+    }
+
+    async fn encode_component<P: EncodePacketComponent<()>>(
+        &mut self,
+        component: &(impl Borrow<P::ComponentType> + ?Sized + Sync),
+    ) -> DraxResult<()> {
+        instrumented!("encode", P, P::encode(component.borrow(), &mut (), self))
+    }
+
+    async fn encode_own_component<P: PacketComponent<(), ComponentType = P>>(
+        &mut self,
+        component: &P,
+    ) -> DraxResult<()> {
+        instrumented!("encode", P, P::encode(component, &mut (), self))
+    }
+
+    async fn encode_component_flush<P: EncodePacketComponent<()>>(
+        &mut self,
+        component: &(impl Borrow<P::ComponentType> + ?Sized + Sync),
+    ) -> DraxResult<()> {
+        self.encode_component::<P>(component).await?;
+        self.flush().await?;
+        Ok(())
+    }
+
+    async fn encode_component_and_close<P: EncodePacketComponent<()>>(
+        mut self,
+        component: &(impl Borrow<P::ComponentType> + ?Sized + Sync),
+    ) -> DraxResult<()>
+    where
+        Self: Sized,
+    {
+        self.encode_component_flush::<P>(component).await?;
+        self.shutdown().await?;
+        Ok(())
     }
 
-    fn write_var_long(&mut self, value: i64) -> WriteVarLong<'_, Self> {
-        primitive::write_var_long(self, value)
+    async fn encode_slice<P: PacketComponent<()>>(
+        &mut self,
+        slice: &[P::ComponentType],
+    ) -> DraxResult<()> {
+        instrumented!("encode", [P], async {
+            self.write_var_int(slice.len() as i32).await?;
+            for item in slice {
+                P::encode(item, &mut (), self).await?;
+            }
+            Ok(())
+        })
     }
 
-    async fn encode_component<P: PacketComponent<()>>(
+    async fn encode_with_len_prefix<P: PacketComponent<()>>(
         &mut self,
         component: &P::ComponentType,
     ) -> DraxResult<()> {
-        P::encode(component, &mut (), self).await
+        let mut buffer = take_scratch();
+        let result = instrumented!("encode", P, async {
+            P::encode(component, &mut (), &mut buffer).await?;
+            let (prefix, prefix_len) = primitive::var_int_bytes(buffer.len() as i32);
+            self.write_all(&prefix[..prefix_len]).await?;
+            self.write_all(&buffer).await?;
+            Ok(())
+        });
+        return_scratch(buffer);
+        result
     }
 
-    async fn encode_own_component<P: PacketComponent<(), ComponentType = P>>(
+    async fn encode_framed<P: PacketComponent<()>>(
         &mut self,
-        component: &P,
+        component: &P::ComponentType,
     ) -> DraxResult<()> {
-        P::encode(component, &mut (), self).await
+        instrumented!("encode", P, async {
+            let buffer = frame_component::<P, ()>(component, &mut ()).await?;
+            self.write_all(&buffer).await?;
+            Ok(())
+        })
     }
 }
 
 #[cfg(feature = "context")]
 pub mod context {
     use crate::prelude::{DraxResult, PacketComponent};
+    use crate::transport::{DecodePacketComponent, EncodePacketComponent};
+    use std::borrow::Borrow;
     use tokio::io::{AsyncRead, AsyncWrite};
 
     /// A wrapper around a writer to streamline the process of encoding packet components
@@ -174,12 +1152,29 @@ pub mod context {
         &'a mut C,
     );
 
-    impl<W: AsyncWrite + Unpin + Send + Sync + ?Sized, C: Send + Sync> ContextWrappedWriter<'_, W, C> {
-        pub async fn encode_component<P: PacketComponent<C>>(
+    impl<'a, W: AsyncWrite + Unpin + Send + Sync + ?Sized, C: Send + Sync>
+        ContextWrappedWriter<'a, W, C>
+    {
+        /// Returns the wrapped writer, allowing raw writes between components.
+        pub fn writer(&mut self) -> &mut W {
+            self.0
+        }
+
+        /// Returns the context components are encoded with.
+        pub fn context(&mut self) -> &mut C {
+            self.1
+        }
+
+        /// Consumes the wrapper, returning the writer and the context.
+        pub fn into_parts(self) -> (&'a mut W, &'a mut C) {
+            (self.0, self.1)
+        }
+
+        pub async fn encode_component<P: EncodePacketComponent<C>>(
             &mut self,
-            component: &P::ComponentType,
+            component: &(impl Borrow<P::ComponentType> + ?Sized + Sync),
         ) -> DraxResult<()> {
-            P::encode(component, self.1, self.0).await
+            P::encode(component.borrow(), self.1, self.0).await
         }
 
         pub async fn encode_own_component<P: PacketComponent<C, ComponentType = P>>(
@@ -213,8 +1208,25 @@ pub mod context {
         &'a mut C,
     );
 
-    impl<R: AsyncRead + Unpin + Send + Sync + ?Sized, C: Send + Sync> ContextWrappedReader<'_, R, C> {
-        pub async fn decode_component<P: PacketComponent<C> + Sized>(
+    impl<'a, R: AsyncRead + Unpin + Send + Sync + ?Sized, C: Send + Sync>
+        ContextWrappedReader<'a, R, C>
+    {
+        /// Returns the wrapped reader, allowing raw reads between components.
+        pub fn reader(&mut self) -> &mut R {
+            self.0
+        }
+
+        /// Returns the context components are decoded with.
+        pub fn context(&mut self) -> &mut C {
+            self.1
+        }
+
+        /// Consumes the wrapper, returning the reader and the context.
+        pub fn into_parts(self) -> (&'a mut R, &'a mut C) {
+            (self.0, self.1)
+        }
+
+        pub async fn decode_component<P: DecodePacketComponent<C> + Sized>(
             &mut self,
         ) -> DraxResult<P::ComponentType> {
             P::decode(self.1, self.0).await
@@ -251,3 +1263,943 @@ pub mod context {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{
+        DraxReadExt, DraxResult, DraxWriteExt, EncodedPacket, IdentifiedPacket, LengthDelimited,
+        Maybe, Metrics, PacketComponent, PacketComponentShared, PollComponent, Polled, Size,
+        SizeCache, TransportError, VarInt,
+    };
+    use crate::transport::{
+        decode_header_then_stream, decode_metered, decode_packet, encode_framed_metered,
+        encode_metered, freeze_encode, measured_encode, take_scratch,
+    };
+    use std::assert_matches::assert_matches;
+    use std::cmp::Ordering;
+    use std::io::Cursor;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufWriter, ReadBuf};
+
+    struct MisreportedSize;
+
+    impl<C: Send + Sync> PacketComponent<C> for MisreportedSize {
+        type ComponentType = i32;
+
+        async fn decode<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+            context: &mut C,
+            read: &mut A,
+        ) -> DraxResult<Self::ComponentType> {
+            VarInt::decode(context, read).await
+        }
+
+        async fn encode<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+            component_ref: &Self::ComponentType,
+            context: &mut C,
+            write: &mut A,
+        ) -> DraxResult<()> {
+            VarInt::encode(component_ref, context, write).await
+        }
+
+        fn size(_: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+            Ok(Size::Constant(1))
+        }
+    }
+
+    struct Scaled;
+
+    impl PacketComponent<i32> for Scaled {
+        type ComponentType = i32;
+
+        async fn decode<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+            context: &mut i32,
+            read: &mut A,
+        ) -> DraxResult<Self::ComponentType> {
+            Self::decode_shared(context, read).await
+        }
+
+        async fn encode<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+            component_ref: &Self::ComponentType,
+            context: &mut i32,
+            write: &mut A,
+        ) -> DraxResult<()> {
+            let scale = *context;
+            VarInt::encode(&(component_ref / scale), context, write).await
+        }
+
+        fn size(input: &Self::ComponentType, context: &mut i32) -> DraxResult<Size> {
+            let scale = *context;
+            VarInt::size(&(input / scale), context)
+        }
+    }
+
+    impl PacketComponentShared<i32> for Scaled {
+        async fn decode_shared<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+            context: &i32,
+            read: &mut A,
+        ) -> DraxResult<Self::ComponentType> {
+            Ok(VarInt::decode_shared(context, read).await? * context)
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_concurrent_shared_decode() -> DraxResult<()> {
+        let mut scale = 10;
+        let mut first = Cursor::new(vec![]);
+        Scaled::encode(&3000, &mut scale, &mut first).await?;
+        let mut second = Cursor::new(vec![]);
+        String::encode(&"test".to_string(), &mut scale, &mut second).await?;
+        first.set_position(0);
+        second.set_position(0);
+
+        let context = scale;
+        let (scaled, string) = tokio::join!(
+            Scaled::decode_shared(&context, &mut first),
+            String::decode_shared(&context, &mut second)
+        );
+        assert_eq!(scaled?, 3000);
+        assert_eq!(string?, "test");
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_size_saturates() {
+        let near_max = Size::Dynamic(usize::MAX - 1);
+        assert_eq!(near_max + Size::Constant(10), Size::Dynamic(usize::MAX));
+        assert_eq!(near_max + 10, Size::Dynamic(usize::MAX));
+        assert_eq!(
+            Size::Constant(usize::MAX) + Size::Constant(1),
+            Size::Constant(usize::MAX)
+        );
+    }
+
+    #[test]
+    pub fn test_size_usize_conversions() {
+        assert_eq!(Size::from(12), Size::Dynamic(12));
+        let size: Size = 0.into();
+        assert_eq!(size, Size::Dynamic(0));
+
+        assert_eq!(usize::from(Size::Dynamic(7)), 7);
+        assert_eq!(usize::from(Size::Constant(7)), 7);
+        assert_eq!(Size::Constant(7).as_usize(), Size::Dynamic(7).as_usize());
+        let bytes: usize = (Size::Constant(3) + 4).into();
+        assert_eq!(bytes, 7);
+    }
+
+    #[test]
+    pub fn test_size_checked_add() {
+        assert_matches!(
+            Size::Constant(1).checked_add(Size::Constant(2)),
+            Ok(Size::Constant(3))
+        );
+        assert_matches!(
+            Size::Dynamic(usize::MAX - 1).checked_add(Size::Constant(1)),
+            Ok(Size::Dynamic(usize::MAX))
+        );
+        assert_matches!(
+            Size::Dynamic(usize::MAX - 1).checked_add(Size::Constant(2)),
+            Err(TransportError::SizeOverflow)
+        );
+    }
+
+    struct MeasuredConstant;
+
+    impl PacketComponent<usize> for MeasuredConstant {
+        type ComponentType = i32;
+
+        async fn decode<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+            context: &mut usize,
+            read: &mut A,
+        ) -> DraxResult<Self::ComponentType> {
+            i32::decode(context, read).await
+        }
+
+        async fn encode<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+            component_ref: &Self::ComponentType,
+            context: &mut usize,
+            write: &mut A,
+        ) -> DraxResult<()> {
+            i32::encode(component_ref, context, write).await
+        }
+
+        fn size(_: &Self::ComponentType, context: &mut usize) -> DraxResult<Size> {
+            *context += 1;
+            Ok(Size::Constant(4))
+        }
+    }
+
+    #[test]
+    pub fn test_size_cache_constant() -> DraxResult<()> {
+        let mut measurements = 0;
+        let mut cache = SizeCache::<MeasuredConstant>::new();
+        assert_eq!(cache.size(&10, &mut measurements)?, Size::Constant(4));
+        assert_eq!(cache.size(&20, &mut measurements)?, Size::Constant(4));
+        assert_eq!(cache.size(&30, &mut measurements)?, Size::Constant(4));
+        assert_eq!(measurements, 3);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_size_cache_per_value_constant() -> DraxResult<()> {
+        let mut cache = SizeCache::<Maybe<i32>>::new();
+        assert_eq!(cache.size(&None, &mut ())?, Size::Constant(1));
+        assert_eq!(cache.size(&Some(10), &mut ())?, Size::Constant(5));
+        assert_eq!(cache.size(&None, &mut ())?, Size::Constant(1));
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_size_cache_fixed_and_dynamic() -> DraxResult<()> {
+        assert_eq!(<i64 as PacketComponent<()>>::FIXED_SIZE, Some(8));
+        assert_eq!(<VarInt as PacketComponent<()>>::FIXED_SIZE, None);
+
+        let mut cache = SizeCache::<VarInt>::new();
+        assert_eq!(cache.size(&1, &mut ())?, Size::Dynamic(1));
+        assert_eq!(cache.size(&300, &mut ())?, Size::Dynamic(2));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_encode_framed() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_framed::<String>(&"test string".to_string())
+            .await?;
+        cursor.set_position(0);
+
+        let frame_size = cursor.read_var_int().await?;
+        assert_eq!(frame_size, 12);
+        assert_eq!(cursor.get_ref().len(), 13);
+        assert_eq!(cursor.decode_component::<String>().await?, "test string");
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_decode_component_with_bytes() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<Vec<String>>(&vec!["a".to_string(), "bc".to_string()])
+            .await?;
+        cursor.write_var_int(300).await?;
+        cursor.set_position(0);
+
+        let (value, bytes) = cursor.decode_component_with_bytes::<Vec<String>>().await?;
+        assert_eq!(bytes.len(), 6);
+        let back = bytes.as_slice().decode_component::<Vec<String>>().await?;
+        assert_eq!(back, value);
+        assert_eq!(cursor.read_var_int().await?, 300);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_decode_packet_skips_trailing() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(300).await?;
+        cursor
+            .encode_component::<String>(&"newer field".to_string())
+            .await?;
+        let declared_len = cursor.get_ref().len() as u64;
+        cursor.write_var_int(10).await?;
+        cursor.set_position(0);
+
+        let back = decode_packet::<VarInt, _, _>(&mut (), &mut cursor, declared_len).await?;
+        assert_eq!(back, 300);
+        assert_eq!(cursor.read_var_int().await?, 10);
+
+        let mut bytes: &[u8] = &[172, 2, 0];
+        let error = decode_packet::<VarInt, _, _>(&mut (), &mut bytes, 4).await;
+        assert!(error.is_err_and(|e| e.is_eof()));
+        Ok(())
+    }
+
+    #[derive(Debug, Eq, PartialEq)]
+    struct TransferHeader {
+        name: String,
+        len: u64,
+    }
+
+    impl<C: Send + Sync> PacketComponent<C> for TransferHeader {
+        type ComponentType = Self;
+
+        async fn decode<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+            context: &mut C,
+            read: &mut A,
+        ) -> DraxResult<Self::ComponentType> {
+            Ok(TransferHeader {
+                name: String::decode(context, read).await?,
+                len: u64::decode(context, read).await?,
+            })
+        }
+
+        async fn encode<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+            component_ref: &Self::ComponentType,
+            context: &mut C,
+            write: &mut A,
+        ) -> DraxResult<()> {
+            String::encode(&component_ref.name, context, write).await?;
+            u64::encode(&component_ref.len, context, write).await
+        }
+
+        fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+            Ok(String::size(&input.name, context)? + 8)
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_decode_header_then_stream() -> DraxResult<()> {
+        let header = TransferHeader {
+            name: "world.zip".to_string(),
+            len: 1024,
+        };
+        let body: Vec<u8> = (0..1024).map(|i| i as u8).collect();
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<TransferHeader>(&header).await?;
+        cursor.write_all(&body).await?;
+        cursor.write_all(&[99]).await?;
+        cursor.set_position(0);
+
+        let (back, read) =
+            decode_header_then_stream::<TransferHeader, _, _>(&mut (), &mut cursor).await?;
+        assert_eq!(back, header);
+
+        let mut streamed = vec![];
+        let copied = tokio::io::copy(&mut read.take(back.len), &mut streamed).await?;
+        assert_eq!(copied, 1024);
+        assert_eq!(streamed, body);
+        assert_eq!(cursor.read_u8().await?, 99);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_decode_or_default_present() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![172, 2, 0, 0, 0, 10]);
+        assert_eq!(cursor.decode_or_default::<VarInt>().await?, 300);
+        assert_eq!(cursor.decode_or_default::<i32>().await?, 10);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_decode_or_default_absent() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![172, 2]);
+        assert_eq!(cursor.decode_component::<VarInt>().await?, 300);
+        assert_eq!(cursor.decode_or_default::<VarInt>().await?, 0);
+        assert_eq!(cursor.decode_or_default::<i32>().await?, 0);
+        assert_eq!(cursor.decode_or_default::<String>().await?, "");
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_decode_or_default_truncated() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![0, 0]);
+        let error = cursor.decode_or_default::<i32>().await;
+        assert!(error.is_err_and(|error| error.is_eof()));
+
+        let mut cursor = Cursor::new(vec![172]);
+        let error = cursor.decode_or_default::<VarInt>().await;
+        assert_matches!(error, Err(TransportError::EOF));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_encode_slice() -> DraxResult<()> {
+        let values = vec![
+            "first".to_string(),
+            "second".to_string(),
+            "third".to_string(),
+        ];
+
+        let mut from_vec = Cursor::new(vec![]);
+        from_vec.encode_component::<Vec<String>>(&values).await?;
+
+        let mut from_slice = Cursor::new(vec![]);
+        from_slice.encode_slice::<String>(&values).await?;
+        assert_eq!(from_slice.get_ref(), from_vec.get_ref());
+
+        let mut sub_slice = Cursor::new(vec![]);
+        sub_slice.encode_slice::<String>(&values[1..]).await?;
+        sub_slice.set_position(0);
+        assert_eq!(
+            sub_slice.decode_component::<Vec<String>>().await?,
+            &values[1..]
+        );
+        Ok(())
+    }
+
+    struct CapturedBody;
+
+    impl crate::transport::DecodePacketComponent<()> for CapturedBody {
+        type ComponentType = Vec<u8>;
+
+        async fn decode<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+            _: &mut (),
+            read: &mut A,
+        ) -> DraxResult<Self::ComponentType> {
+            let mut body = vec![];
+            read.read_to_end(&mut body).await?;
+            Ok(body)
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_decode_only_component() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![1, 2, 3]);
+        assert_eq!(
+            cursor.decode_component::<CapturedBody>().await?,
+            vec![1, 2, 3]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_read_exact_limited() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![1, 2, 3, 4]);
+        assert_eq!(cursor.read_exact_limited(3, 3).await?, vec![1, 2, 3]);
+        assert!(cursor.read_exact_limited(0, 0).await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_read_exact_limited_over_max() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![1, 2, 3, 4]);
+        assert_matches!(
+            cursor.read_exact_limited(4, 3).await,
+            Err(TransportError::LimitExceeded(3, 4, "reading bytes"))
+        );
+        assert_matches!(
+            cursor.read_exact_limited(usize::MAX, 3).await,
+            Err(TransportError::LimitExceeded(3, i32::MAX, _))
+        );
+        assert_eq!(cursor.position(), 0);
+        Ok(())
+    }
+
+    #[cfg(feature = "context")]
+    #[tokio::test]
+    pub async fn test_context_wrapper_parts() -> DraxResult<()> {
+        use crate::prelude::{ReaderContext, WriterContext};
+
+        let mut scale = 2;
+        let mut cursor = Cursor::new(vec![]);
+        let mut writer = cursor.writer_context(&mut scale);
+        writer.writer().write_var_int(300).await?;
+        writer.encode_component::<Scaled>(&42).await?;
+        *writer.context() = 3;
+        writer.encode_component::<Scaled>(&42).await?;
+        let (_, scale_ref) = writer.into_parts();
+        assert_eq!(*scale_ref, 3);
+        assert_eq!(cursor.get_ref(), &vec![172, 2, 21, 14]);
+        cursor.set_position(0);
+
+        let mut reader = cursor.reader_context(&mut scale);
+        assert_eq!(reader.reader().read_var_int().await?, 300);
+        *reader.context() = 2;
+        assert_eq!(reader.decode_component::<Scaled>().await?, 42);
+        *reader.context() = 3;
+        assert_eq!(reader.decode_component::<Scaled>().await?, 42);
+        let (read, _) = reader.into_parts();
+        assert_eq!(read.position(), 4);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_size_ordering() {
+        assert_eq!(Size::Constant(5).bytes(), Size::Dynamic(5).bytes());
+        assert!(Size::Dynamic(5) < Size::Constant(5));
+        assert!(Size::Constant(5) < Size::Dynamic(10));
+        assert!(Size::Dynamic(10) > Size::Dynamic(5));
+        assert_eq!(
+            [Size::Dynamic(10), Size::Constant(5), Size::Dynamic(5)]
+                .into_iter()
+                .max(),
+            Some(Size::Dynamic(10))
+        );
+        assert_eq!(Size::Dynamic(5).cmp(&Size::Dynamic(5)), Ordering::Equal);
+    }
+
+    #[tokio::test]
+    pub async fn test_encoded_packet() -> DraxResult<()> {
+        let values = vec!["broadcast".to_string(), "packet".to_string()];
+        let packet = EncodedPacket::<Vec<String>, ()>::new(&values, &mut ()).await?;
+
+        let mut expected = Cursor::new(vec![]);
+        expected.encode_component::<Vec<String>>(&values).await?;
+        assert_eq!(packet.as_bytes(), expected.get_ref().as_slice());
+
+        let mut writers = [vec![], vec![], vec![]];
+        for writer in writers.iter_mut() {
+            packet.write_to(writer).await?;
+        }
+        for writer in writers {
+            assert_eq!(&writer, expected.get_ref());
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_reserve_var_int() -> DraxResult<()> {
+        let values = vec!["patched".to_string(), "length".to_string()];
+
+        let mut cursor = Cursor::new(vec![]);
+        let patch = cursor.reserve_var_int().await?;
+        let start = cursor.position();
+        cursor.encode_component::<Vec<String>>(&values).await?;
+        let len = (cursor.position() - start) as i32;
+        patch.finish(&mut cursor, len).await?;
+        cursor.encode_component::<VarInt>(&300).await?;
+
+        cursor.set_position(0);
+        assert_eq!(
+            cursor
+                .decode_component::<LengthDelimited<Vec<String>>>()
+                .await?,
+            values
+        );
+        assert_eq!(cursor.decode_component::<VarInt>().await?, 300);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_encode_component_flush() -> DraxResult<()> {
+        let (client, mut server) = tokio::io::duplex(64);
+        let mut client = BufWriter::new(client);
+
+        client.encode_component::<VarInt>(&300).await?;
+        assert_eq!(client.buffer(), &[172, 2]);
+
+        client.encode_component_flush::<VarInt>(&25).await?;
+        assert_eq!(server.decode_component::<VarInt>().await?, 300);
+        assert_eq!(server.decode_component::<VarInt>().await?, 25);
+        assert!(client.buffer().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_encode_component_and_close() -> DraxResult<()> {
+        let (client, mut server) = tokio::io::duplex(64);
+        let client = BufWriter::new(client);
+        client
+            .encode_component_and_close::<String>(&"ping".to_string())
+            .await?;
+
+        assert_eq!(server.decode_component::<String>().await?, "ping");
+        let mut rest = vec![];
+        assert_eq!(server.read_to_end(&mut rest).await?, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_encode_through_reference() -> DraxResult<()> {
+        let values = ["first".to_string(), "second".to_string()];
+
+        let mut cursor = Cursor::new(vec![]);
+        for value in values.iter().filter(|value| !value.is_empty()) {
+            let value: &&String = &value;
+            cursor.encode_component::<String>(value).await?;
+        }
+        cursor.set_position(0);
+
+        assert_eq!(cursor.decode_component::<String>().await?, values[0]);
+        assert_eq!(cursor.decode_component::<String>().await?, values[1]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_encode_with_len_prefix() -> DraxResult<()> {
+        let values = (0..200).map(|x| x * 1000).collect::<Vec<i32>>();
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_with_len_prefix::<Vec<VarInt>>(&values)
+            .await?;
+        cursor.set_position(0);
+
+        let prefix = cursor.read_var_int().await?;
+        let body_start = cursor.position() as usize;
+        assert_eq!(prefix as usize, cursor.get_ref().len() - body_start);
+        assert_eq!(cursor.decode_component::<Vec<VarInt>>().await?, values);
+
+        let mut framed = Cursor::new(vec![]);
+        framed.encode_framed::<Vec<VarInt>>(&values).await?;
+        assert_eq!(framed.get_ref(), cursor.get_ref());
+
+        let scratch = take_scratch();
+        assert!(scratch.is_empty());
+        assert!(scratch.capacity() >= prefix as usize);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_empty_frame() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_framed::<()>(&()).await?;
+        assert_eq!(cursor.get_ref(), &vec![0]);
+        cursor.set_position(0);
+
+        cursor.decode_framed::<()>().await?;
+        assert_eq!(cursor.position(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_keep_alive_frames() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_framed::<()>(&()).await?;
+        cursor.encode_framed::<()>(&()).await?;
+        cursor.encode_framed::<VarInt>(&300).await?;
+        cursor.set_position(0);
+
+        cursor.decode_framed::<()>().await?;
+        cursor.decode_framed::<()>().await?;
+        assert_eq!(cursor.decode_framed::<VarInt>().await?, 300);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_empty_frame_non_empty_component() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![0, 172, 2]);
+
+        let error = cursor.decode_framed::<VarInt>().await;
+
+        assert_matches!(error, Err(TransportError::EOF));
+        assert_eq!(cursor.position(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_freeze_encode_broadcast() -> DraxResult<()> {
+        let frozen = freeze_encode::<Vec<String>, ()>(&vec!["hi".to_string()], &mut ()).await?;
+        let writers = (0..3).map(|_| {
+            let bytes = frozen.clone();
+            tokio::spawn(async move {
+                let mut writer = vec![];
+                writer.write_all(&bytes).await?;
+                DraxResult::Ok(writer)
+            })
+        });
+
+        let mut received = vec![];
+        for writer in writers.collect::<Vec<_>>() {
+            received.push(writer.await.unwrap()?);
+        }
+        assert_eq!(received, vec![frozen.to_vec(); 3]);
+        assert_eq!(frozen.as_ref(), &[1, 2, b'h', b'i']);
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_encode_framed_size_mismatch() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        let error = cursor.encode_framed::<MisreportedSize>(&55324).await;
+
+        assert_matches!(error, Err(TransportError::SizeMismatch(1, 3)));
+        assert!(cursor.get_ref().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_measured_encode_ignores_size() -> DraxResult<()> {
+        let (bytes, len) = measured_encode::<MisreportedSize, ()>(&55324, &mut ()).await?;
+        assert_eq!(len, 3);
+        assert_eq!(bytes.len(), len);
+
+        let mut cursor = Cursor::new(vec![]);
+        cursor
+            .encode_component::<LengthDelimited<MisreportedSize>>(&55324)
+            .await?;
+        assert_eq!(cursor.get_ref()[0], 3);
+        cursor.set_position(0);
+        let back = cursor
+            .decode_component::<LengthDelimited<MisreportedSize>>()
+            .await?;
+        assert_eq!(back, 55324);
+        Ok(())
+    }
+
+    /// A reader which yields a single byte per read, returning `Poll::Pending` before each one.
+    struct Trickle {
+        bytes: Vec<u8>,
+        position: usize,
+        ready: bool,
+    }
+
+    impl AsyncRead for Trickle {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            if !self.ready {
+                self.ready = true;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            self.ready = false;
+            if let Some(&byte) = self.bytes.get(self.position) {
+                self.position += 1;
+                buf.put_slice(&[byte]);
+            }
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    struct Point;
+
+    impl<C: Send + Sync> PollComponent<C> for Point {
+        type ComponentType = (i32, i32);
+        type State = ([u8; 8], usize);
+
+        const FIXED_SIZE: Option<usize> = Some(8);
+
+        fn poll_decode<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+            (buf, filled): &mut Self::State,
+            _: &mut C,
+            cx: &mut Context<'_>,
+            read: &mut A,
+        ) -> Poll<DraxResult<Self::ComponentType>> {
+            while *filled < buf.len() {
+                let mut read_buf = ReadBuf::new(&mut buf[*filled..]);
+                std::task::ready!(Pin::new(&mut *read).poll_read(cx, &mut read_buf))?;
+                if read_buf.filled().is_empty() {
+                    return Poll::Ready(Err(TransportError::EOF));
+                }
+                *filled += read_buf.filled().len();
+            }
+            let x = i32::from_be_bytes(buf[..4].try_into().unwrap());
+            let y = i32::from_be_bytes(buf[4..].try_into().unwrap());
+            Poll::Ready(Ok((x, y)))
+        }
+
+        async fn encode<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+            component_ref: &Self::ComponentType,
+            context: &mut C,
+            write: &mut A,
+        ) -> DraxResult<()> {
+            i32::encode(&component_ref.0, context, write).await?;
+            i32::encode(&component_ref.1, context, write).await
+        }
+
+        fn size(_: &Self::ComponentType, _: &mut C) -> DraxResult<Size> {
+            Ok(Size::Constant(8))
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_poll_component_resumes() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.encode_component::<Polled<Point>>(&(-3, 300)).await?;
+        assert_eq!(<Polled<Point> as PacketComponent<()>>::FIXED_SIZE, Some(8));
+
+        let mut trickle = Trickle {
+            bytes: cursor.into_inner(),
+            position: 0,
+            ready: false,
+        };
+        let back = trickle.decode_component::<Polled<Point>>().await?;
+        assert_eq!(back, (-3, 300));
+
+        let error = trickle.decode_component::<Polled<Point>>().await;
+        assert!(error.is_err_and(|e| e.is_eof()));
+        Ok(())
+    }
+
+    #[derive(Debug, Eq, PartialEq)]
+    struct Ping(i64);
+
+    impl<C: Send + Sync> PacketComponent<C> for Ping {
+        type ComponentType = Self;
+
+        async fn decode<A: AsyncRead + Unpin + Send + Sync + ?Sized>(
+            context: &mut C,
+            read: &mut A,
+        ) -> DraxResult<Self::ComponentType> {
+            Ok(Ping(i64::decode(context, read).await?))
+        }
+
+        async fn encode<A: AsyncWrite + Unpin + Send + Sync + ?Sized>(
+            component_ref: &Self::ComponentType,
+            context: &mut C,
+            write: &mut A,
+        ) -> DraxResult<()> {
+            i64::encode(&component_ref.0, context, write).await
+        }
+
+        fn size(input: &Self::ComponentType, context: &mut C) -> DraxResult<Size> {
+            i64::size(&input.0, context)
+        }
+    }
+
+    impl<C: Send + Sync> IdentifiedPacket<C> for Ping {
+        const PACKET_ID: i32 = 300;
+    }
+
+    #[tokio::test]
+    pub async fn test_identified_packet() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        Ping::encode_with_id(&Ping(10), &mut (), &mut cursor).await?;
+        cursor.set_position(0);
+
+        assert_eq!(
+            Ping::decode_expecting_id(&mut (), &mut cursor).await?,
+            Ping(10)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_identified_packet_wrong_id() -> DraxResult<()> {
+        let mut cursor = Cursor::new(vec![]);
+        cursor.write_var_int(301).await?;
+        cursor.encode_own_component(&Ping(10)).await?;
+        cursor.set_position(0);
+
+        let error = Ping::decode_expecting_id(&mut (), &mut cursor).await;
+
+        assert_matches!(error, Err(TransportError::UnexpectedPacketId(300, 301)));
+        Ok(())
+    }
+
+    #[derive(Default)]
+    struct CountingMetrics {
+        bytes_read: usize,
+        bytes_written: usize,
+        packets_decoded: usize,
+        packets_encoded: usize,
+    }
+
+    impl Metrics for CountingMetrics {
+        fn on_bytes_read(&mut self, n: usize) {
+            self.bytes_read += n;
+        }
+
+        fn on_bytes_written(&mut self, n: usize) {
+            self.bytes_written += n;
+        }
+
+        fn on_packet_decoded(&mut self) {
+            self.packets_decoded += 1;
+        }
+
+        fn on_packet_encoded(&mut self) {
+            self.packets_encoded += 1;
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_metrics_round_trip() -> DraxResult<()> {
+        let mut metrics = CountingMetrics::default();
+        let mut cursor = Cursor::new(vec![]);
+        encode_metered::<VarInt, _, _>(&55324, &mut metrics, &mut cursor).await?;
+        encode_metered::<String, _, _>(&"test".to_string(), &mut metrics, &mut cursor).await?;
+        encode_framed_metered::<Ping, _, _>(&Ping(10), &mut metrics, &mut cursor).await?;
+        cursor.set_position(0);
+
+        assert_eq!(metrics.bytes_written, 3 + 5 + 9);
+        assert_eq!(metrics.packets_encoded, 3);
+
+        assert_eq!(
+            decode_metered::<VarInt, _, _>(&mut metrics, &mut cursor).await?,
+            55324
+        );
+        assert_eq!(
+            decode_metered::<String, _, _>(&mut metrics, &mut cursor).await?,
+            "test"
+        );
+        assert_eq!(
+            decode_metered::<VarInt, _, _>(&mut metrics, &mut cursor).await?,
+            8
+        );
+        assert_eq!(
+            decode_metered::<Ping, _, _>(&mut metrics, &mut cursor).await?,
+            Ping(10)
+        );
+
+        assert_eq!(metrics.bytes_read, metrics.bytes_written);
+        assert_eq!(metrics.packets_decoded, 4);
+
+        let error = decode_metered::<VarInt, _, _>(&mut metrics, &mut cursor).await;
+        assert!(error.is_err());
+        assert_eq!(metrics.packets_decoded, 4);
+        Ok(())
+    }
+
+    #[cfg(feature = "tracing")]
+    mod tracing_spans {
+        use crate::prelude::{DraxReadExt, DraxResult, DraxWriteExt, VarInt};
+        use std::io::Cursor;
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::Subscriber;
+        use tracing_subscriber::layer::{Context, SubscriberExt};
+        use tracing_subscriber::registry::LookupSpan;
+        use tracing_subscriber::Layer;
+
+        #[derive(Debug, Default)]
+        struct RecordedSpan {
+            name: &'static str,
+            component: String,
+            error: Option<String>,
+        }
+
+        impl Visit for RecordedSpan {
+            fn record_str(&mut self, field: &Field, value: &str) {
+                if field.name() == "component" {
+                    self.component = value.to_string();
+                }
+            }
+
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "error" {
+                    self.error = Some(format!("{value:?}"));
+                }
+            }
+        }
+
+        #[derive(Clone, Default)]
+        struct RecordingLayer(Arc<Mutex<Vec<(Id, RecordedSpan)>>>);
+
+        impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for RecordingLayer {
+            fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _: Context<'_, S>) {
+                let mut span = RecordedSpan {
+                    name: attrs.metadata().name(),
+                    ..Default::default()
+                };
+                attrs.record(&mut span);
+                self.0.lock().unwrap().push((id.clone(), span));
+            }
+
+            fn on_record(&self, id: &Id, values: &Record<'_>, _: Context<'_, S>) {
+                let mut spans = self.0.lock().unwrap();
+                if let Some((_, span)) = spans.iter_mut().find(|(span_id, _)| span_id == id) {
+                    values.record(span);
+                }
+            }
+        }
+
+        #[tokio::test]
+        pub async fn test_component_spans() -> DraxResult<()> {
+            let layer = RecordingLayer::default();
+            let subscriber = tracing_subscriber::registry().with(layer.clone());
+            let _guard = tracing::subscriber::set_default(subscriber);
+
+            let mut cursor = Cursor::new(vec![]);
+            cursor.encode_component::<VarInt>(&300).await?;
+            cursor.set_position(0);
+            assert_eq!(cursor.decode_component::<VarInt>().await?, 300);
+            assert!(cursor.decode_component::<VarInt>().await.is_err());
+
+            let spans = layer.0.lock().unwrap();
+            let spans = spans.iter().map(|(_, span)| span).collect::<Vec<_>>();
+            assert_eq!(spans.len(), 3);
+            assert_eq!(spans[0].name, "encode");
+            assert_eq!(spans[1].name, "decode");
+            assert_eq!(spans[2].name, "decode");
+            for span in &spans {
+                assert_eq!(span.component, std::any::type_name::<VarInt>());
+            }
+            assert_eq!(spans[1].error, None);
+            assert!(spans[2].error.is_some());
+            Ok(())
+        }
+    }
+}